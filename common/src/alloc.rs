@@ -0,0 +1,172 @@
+// Global allocator which counts every allocation made by a solution, so
+// that the report for each part can say how much memory it used. Counting
+// is opt-in as it slows down every allocation; build a day with
+//
+//   cargo run --features common/count-allocations -- --report report.json
+//
+// to install it. Without the feature nothing is counted and the report
+// leaves the allocation figures out.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static TOTAL: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator, keeping count of what passes through it
+pub struct Counting;
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn record_alloc(size: usize) {
+    COUNT.fetch_add(1, Ordering::Relaxed);
+    TOTAL.fetch_add(size, Ordering::Relaxed);
+
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    CURRENT.fetch_sub(size, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Whether allocations are being counted in this build
+pub fn enabled() -> bool {
+    cfg!(feature = "count-allocations")
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Allocations {
+    /// Most heap memory in use at once, above what was
+    /// already in use when the measurement started
+    pub peak_bytes: usize,
+    /// Number of allocations, counting each reallocation as one
+    pub allocations: usize,
+    /// Bytes requested over all allocations
+    pub allocated_bytes: usize,
+}
+
+impl Allocations {
+    /// Combine measurements of the same part taken at different times
+    pub fn merge(self, other: Allocations) -> Allocations {
+        Allocations {
+            peak_bytes: self.peak_bytes.max(other.peak_bytes),
+            allocations: self.allocations + other.allocations,
+            allocated_bytes: self.allocated_bytes + other.allocated_bytes,
+        }
+    }
+}
+
+/// Counts taken at the start of a section of code being measured
+pub struct Measurement {
+    current: usize,
+    count: usize,
+    total: usize,
+}
+
+impl Measurement {
+    /// Start measuring. Measurements cannot be nested as
+    /// starting one resets the peak of any other
+    pub fn start() -> Measurement {
+        let current = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(current, Ordering::Relaxed);
+
+        Measurement {
+            current,
+            count: COUNT.load(Ordering::Relaxed),
+            total: TOTAL.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn finish(self) -> Allocations {
+        Allocations {
+            peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(self.current),
+            allocations: COUNT.load(Ordering::Relaxed) - self.count,
+            allocated_bytes: TOTAL.load(Ordering::Relaxed) - self.total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_alloc {
+    use super::*;
+
+    #[test]
+    fn test_counting() {
+        let layout = Layout::from_size_align(1024, 8).unwrap();
+        let measurement = Measurement::start();
+
+        unsafe {
+            let ptr = Counting.alloc(layout);
+            let ptr = Counting.realloc(ptr, layout, 4096);
+            Counting.dealloc(ptr, Layout::from_size_align(4096, 8).unwrap());
+        }
+
+        let result = measurement.finish();
+
+        // other tests may be allocating at the same time when counting is enabled
+        assert!(result.peak_bytes >= 4096);
+        assert!(result.allocations >= 2);
+        assert!(result.allocated_bytes >= 1024 + 4096);
+    }
+
+    #[test]
+    fn test_merge() {
+        let first = Allocations {
+            peak_bytes: 100,
+            allocations: 2,
+            allocated_bytes: 150,
+        };
+        let second = Allocations {
+            peak_bytes: 50,
+            allocations: 1,
+            allocated_bytes: 50,
+        };
+
+        let expected = Allocations {
+            peak_bytes: 100,
+            allocations: 3,
+            allocated_bytes: 200,
+        };
+
+        assert_eq!(first.merge(second), expected);
+    }
+}
@@ -0,0 +1,301 @@
+// Harness for the corpus of extra examples under tests/cases at the root of
+// the repository. Each day has a directory of cases, and each case is a
+// directory holding:
+//
+//   input.txt    the puzzle input, given to the solution on stdin
+//   part1.txt    the expected answer to part 1, if it is known
+//   part2.txt    the expected answer to part 2, if it is known
+//   source.txt   where the case came from
+//
+// Every day runs its own cases from an integration test, so adding a case
+// only needs the files above.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::report::Report;
+
+#[derive(Debug, PartialEq)]
+pub struct Case {
+    pub name: String,
+    pub path: PathBuf,
+    pub source: String,
+    /// Expected answer for each part
+    pub expected: BTreeMap<u32, String>,
+}
+
+#[derive(Debug)]
+pub enum CaseError {
+    Io(io::Error),
+    Report(serde_json::Error),
+    MissingFile(PathBuf),
+    NoExpectedAnswers(String),
+    Failed(String, String),
+    WrongAnswer {
+        case: String,
+        part: u32,
+        expected: String,
+        actual: Option<String>,
+    },
+}
+
+impl Display for CaseError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            CaseError::Io(err) => write!(f, "{}", err),
+            CaseError::Report(err) => write!(f, "Invalid report: {}", err),
+            CaseError::MissingFile(path) => write!(f, "Missing file: {}", path.display()),
+            CaseError::NoExpectedAnswers(case) => {
+                write!(f, "Case {} has no expected answers", case)
+            }
+            CaseError::Failed(case, stderr) => write!(f, "Case {} failed: {}", case, stderr),
+            CaseError::WrongAnswer {
+                case,
+                part,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Case {} part {}: expected {:?}, got {:?}",
+                case, part, expected, actual
+            ),
+        }
+    }
+}
+
+impl Error for CaseError {}
+
+impl From<io::Error> for CaseError {
+    fn from(err: io::Error) -> Self {
+        CaseError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for CaseError {
+    fn from(err: serde_json::Error) -> Self {
+        CaseError::Report(err)
+    }
+}
+
+/// Directory holding the cases for the day in the given crate directory
+pub fn directory(manifest_dir: &Path) -> PathBuf {
+    let day = manifest_dir.file_name().unwrap_or_default();
+
+    manifest_dir
+        .join("..")
+        .join("tests")
+        .join("cases")
+        .join(day)
+}
+
+fn read_required(path: PathBuf) -> Result<String, CaseError> {
+    if !path.is_file() {
+        return Err(CaseError::MissingFile(path));
+    }
+    Ok(fs::read_to_string(path)?)
+}
+
+fn load(path: &Path) -> Result<Case, CaseError> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    read_required(path.join("input.txt"))?;
+    let source = read_required(path.join("source.txt"))?;
+
+    let mut expected = BTreeMap::new();
+    for part in 1..=2 {
+        let answer = path.join(format!("part{}.txt", part));
+        if answer.is_file() {
+            let answer = fs::read_to_string(answer)?;
+            expected.insert(part, String::from(answer.trim_end()));
+        }
+    }
+
+    if expected.is_empty() {
+        return Err(CaseError::NoExpectedAnswers(name));
+    }
+
+    Ok(Case {
+        name,
+        path: path.to_path_buf(),
+        source: String::from(source.trim()),
+        expected,
+    })
+}
+
+/// Every case in the given directory, sorted by name.
+/// A day without a directory has no cases
+pub fn discover(dir: &Path) -> Result<Vec<Case>, CaseError> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut paths = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths.iter().map(|path| load(path)).collect()
+}
+
+/// Run the solution on the input of a case, returning its report
+pub fn run(exe: &Path, case: &Case) -> Result<Report, CaseError> {
+    let report_path =
+        std::env::temp_dir().join(format!("case-{}-{}.json", std::process::id(), case.name));
+
+    let output = Command::new(exe)
+        .arg("--report")
+        .arg(&report_path)
+        .stdin(File::open(case.path.join("input.txt"))?)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(CaseError::Failed(
+            case.name.clone(),
+            String::from(stderr.trim()),
+        ));
+    }
+
+    let contents = fs::read_to_string(&report_path)?;
+    fs::remove_file(&report_path)?;
+
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Run a case, checking each answer it expects
+pub fn check(exe: &Path, case: &Case) -> Result<(), CaseError> {
+    let report = run(exe, case)?;
+
+    for (&part, expected) in &case.expected {
+        let actual = report
+            .parts
+            .iter()
+            .find(|p| p.part == part)
+            .and_then(|p| p.answer.clone());
+
+        if actual.as_deref() != Some(expected.as_str()) {
+            return Err(CaseError::WrongAnswer {
+                case: case.name.clone(),
+                part,
+                expected: expected.clone(),
+                actual,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Check every case for a day, panicking with the list of those which
+/// fail. Meant to be called from an integration test of the day with
+/// `env!("CARGO_MANIFEST_DIR")` and `env!("CARGO_BIN_EXE_<name>")`
+pub fn assert_cases(manifest_dir: &str, exe: &str) {
+    let cases = match discover(&directory(Path::new(manifest_dir))) {
+        Ok(cases) => cases,
+        Err(err) => panic!("{}", err),
+    };
+
+    let failures: Vec<String> = cases
+        .iter()
+        .filter_map(|case| check(Path::new(exe), case).err())
+        .map(|err| err.to_string())
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[cfg(test)]
+mod test_cases {
+    use super::*;
+
+    fn write_case(dir: &Path, name: &str, files: &[(&str, &str)]) {
+        let path = dir.join(name);
+        fs::create_dir_all(&path).unwrap();
+        for (file, contents) in files {
+            fs::write(path.join(file), contents).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_directory() {
+        let dir = directory(Path::new("/repo/day09"));
+        assert_eq!(dir, PathBuf::from("/repo/day09/../tests/cases/day09"));
+    }
+
+    #[test]
+    fn test_discover() {
+        let dir = tempfile::tempdir().unwrap();
+
+        write_case(
+            dir.path(),
+            "b-second",
+            &[
+                ("input.txt", "2"),
+                ("part2.txt", "4\n"),
+                ("source.txt", "made up\n"),
+            ],
+        );
+        write_case(
+            dir.path(),
+            "a-first",
+            &[
+                ("input.txt", "1"),
+                ("part1.txt", "#..#\n#..#\n"),
+                ("source.txt", "made up"),
+            ],
+        );
+
+        let cases = discover(dir.path()).unwrap();
+        let names: Vec<&str> = cases.iter().map(|c| c.name.as_str()).collect();
+
+        assert_eq!(names, vec!["a-first", "b-second"]);
+        assert_eq!(cases[0].expected[&1], "#..#\n#..#");
+        assert_eq!(cases[1].expected.keys().collect::<Vec<_>>(), vec![&2]);
+        assert_eq!(cases[1].source, "made up");
+    }
+
+    #[test]
+    fn test_discover_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(discover(&dir.path().join("day99")).unwrap(), vec![]);
+
+        write_case(
+            dir.path(),
+            "no-source",
+            &[("input.txt", "1"), ("part1.txt", "1")],
+        );
+        assert!(matches!(
+            discover(dir.path()),
+            Err(CaseError::MissingFile(_))
+        ));
+    }
+
+    #[test]
+    fn test_no_expected_answers() {
+        let dir = tempfile::tempdir().unwrap();
+
+        write_case(
+            dir.path(),
+            "empty",
+            &[("input.txt", "1"), ("source.txt", "?")],
+        );
+        assert!(matches!(
+            discover(dir.path()),
+            Err(CaseError::NoExpectedAnswers(_))
+        ));
+    }
+}
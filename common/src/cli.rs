@@ -0,0 +1,120 @@
+// Command line options understood by the solution for every day
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Options {
+    /// Print how each answer was derived after the answer itself
+    pub explain: bool,
+    /// Write the answers and timings for each part to this file as JSON
+    pub report: Option<PathBuf>,
+    /// Only solve these parts. Every part is solved when empty
+    pub parts: Vec<u32>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum OptionsError {
+    Unknown(String),
+    MissingValue(String),
+    InvalidPart(String),
+    /// An option and the value given to it which it does not accept
+    InvalidValue(String, String),
+    /// An option which only makes sense along with another
+    Requires(String, String),
+}
+
+impl Display for OptionsError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            OptionsError::Unknown(arg) => write!(f, "Unknown option: {}", arg),
+            OptionsError::MissingValue(arg) => write!(f, "Missing value for option: {}", arg),
+            OptionsError::InvalidPart(value) => write!(f, "Invalid part: {}", value),
+            OptionsError::InvalidValue(arg, value) => {
+                write!(f, "Invalid value for option {}: {}", arg, value)
+            }
+            OptionsError::Requires(arg, other) => write!(f, "Option {} needs {}", arg, other),
+        }
+    }
+}
+
+impl Error for OptionsError {}
+
+impl Options {
+    pub fn from_args() -> Result<Options, OptionsError> {
+        Options::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse<I, S>(args: I) -> Result<Options, OptionsError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            let mut value = || {
+                args.next()
+                    .map(|v| String::from(v.as_ref()))
+                    .ok_or_else(|| OptionsError::MissingValue(String::from(arg)))
+            };
+
+            match arg {
+                "--explain" => options.explain = true,
+                "--report" => options.report = Some(PathBuf::from(value()?)),
+                "--part" => {
+                    let part = value()?;
+                    match part.parse() {
+                        Ok(number @ 1..=2) => options.parts.push(number),
+                        _ => return Err(OptionsError::InvalidPart(part)),
+                    }
+                }
+                unknown => return Err(OptionsError::Unknown(String::from(unknown))),
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Whether the given part should be solved
+    pub fn runs(&self, part: u32) -> bool {
+        self.parts.is_empty() || self.parts.contains(&part)
+    }
+}
+
+#[cfg(test)]
+mod test_options {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(args, expected,
+        case(vec![], Ok(Options::default())),
+        case(vec!["--explain"], Ok(Options { explain: true, ..Options::default() })),
+        case(
+            vec!["--report", "out.json", "--explain"],
+            Ok(Options { explain: true, report: Some(PathBuf::from("out.json")), parts: vec![] })
+        ),
+        case(
+            vec!["--part", "2", "--part", "1"],
+            Ok(Options { parts: vec![2, 1], ..Options::default() })
+        ),
+        case(vec!["--report"], Err(OptionsError::MissingValue(String::from("--report")))),
+        case(vec!["--part", "3"], Err(OptionsError::InvalidPart(String::from("3")))),
+        case(vec!["--bogus"], Err(OptionsError::Unknown(String::from("--bogus")))),
+    )]
+    fn test_parse(args: Vec<&str>, expected: Result<Options, OptionsError>) {
+        assert_eq!(Options::parse(args), expected);
+    }
+
+    #[test]
+    fn test_runs() {
+        let options = Options::default();
+        assert!(options.runs(1) && options.runs(2));
+
+        let options = Options::parse(vec!["--part", "2"]).unwrap();
+        assert!(!options.runs(1) && options.runs(2));
+    }
+}
@@ -0,0 +1,313 @@
+// Detect when a simulation starts repeating itself so that its state
+// far in the future can be worked out without simulating every step.
+// Simulations are described by a step function, which produces the next
+// state from the current one, and a fingerprint of each state. Two states
+// with the same fingerprint are considered to be the same.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Cycle {
+    /// Step at which the first state of the cycle appears
+    pub start: usize,
+    /// Number of steps before a state is seen again
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Earliest step which has the same state as step n
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// Number of complete cycles that run between `reduce(n)` and n
+    pub fn repetitions(&self, n: usize) -> usize {
+        if n < self.start {
+            0
+        } else {
+            (n - self.start) / self.length
+        }
+    }
+
+    /// Extrapolate a value which drifts by the same amount every cycle,
+    /// such as the position of a pattern that moves while repeating.
+    /// `values` holds the value at each step and must cover every step
+    /// up to and including `start + length`.
+    pub fn extrapolate(&self, values: &[i64], n: usize) -> i64 {
+        let drift = values[self.start + self.length] - values[self.start];
+
+        values[self.reduce(n)] + drift * self.repetitions(n) as i64
+    }
+
+    /// State after n steps, only simulating as far as `reduce(n)`
+    pub fn nth<S, F>(&self, initial: S, mut step: F, n: usize) -> S
+    where
+        F: FnMut(&S) -> S,
+    {
+        let mut state = initial;
+        for _ in 0..self.reduce(n) {
+            state = step(&state);
+        }
+        state
+    }
+}
+
+/// Detects cycles by remembering the fingerprint of every state seen
+/// so far. Useful when the simulation mutates itself in place and
+/// cannot be described by a step function.
+#[derive(Debug)]
+pub struct Detector<K> {
+    seen: HashMap<K, usize>,
+    step: usize,
+}
+
+impl<K: Eq + Hash> Default for Detector<K> {
+    fn default() -> Self {
+        Detector::new()
+    }
+}
+
+impl<K: Eq + Hash> Detector<K> {
+    pub fn new() -> Detector<K> {
+        Detector {
+            seen: HashMap::new(),
+            step: 0,
+        }
+    }
+
+    /// Record the fingerprint of the next state in the simulation.
+    /// The first fingerprint observed is step 0.
+    /// Returns the cycle as soon as a fingerprint is observed twice.
+    pub fn observe(&mut self, fingerprint: K) -> Option<Cycle> {
+        let step = self.step;
+        self.step += 1;
+
+        match self.seen.get(&fingerprint) {
+            Some(&start) => Some(Cycle {
+                start,
+                length: step - start,
+            }),
+            None => {
+                self.seen.insert(fingerprint, step);
+                None
+            }
+        }
+    }
+
+    /// Number of fingerprints observed so far
+    pub fn steps(&self) -> usize {
+        self.step
+    }
+}
+
+/// Hash based cycle detection. Stores the fingerprint of every state
+/// visited, so finds the cycle as soon as it repeats for the first time.
+/// Gives up and returns None after `limit` steps.
+pub fn find_cycle<S, F, K, P>(
+    initial: S,
+    mut step: F,
+    mut fingerprint: P,
+    limit: usize,
+) -> Option<Cycle>
+where
+    F: FnMut(&S) -> S,
+    P: FnMut(&S) -> K,
+    K: Eq + Hash,
+{
+    let mut detector = Detector::new();
+    let mut state = initial;
+
+    for _ in 0..=limit {
+        if let Some(cycle) = detector.observe(fingerprint(&state)) {
+            return Some(cycle);
+        }
+        state = step(&state);
+    }
+
+    None
+}
+
+/// Brent's cycle detection algorithm. Uses a constant amount of memory
+/// regardless of how long it takes for the cycle to appear, at the cost
+/// of running the step function more times than `find_cycle`.
+/// Gives up and returns None after `limit` steps.
+pub fn brent<S, F, K, P>(initial: S, mut step: F, mut fingerprint: P, limit: usize) -> Option<Cycle>
+where
+    S: Clone,
+    F: FnMut(&S) -> S,
+    P: FnMut(&S) -> K,
+    K: PartialEq,
+{
+    // First find the length of the cycle. The tortoise teleports to the
+    // hare every time the number of steps reaches a power of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = fingerprint(&initial);
+    let mut hare = step(&initial);
+    let mut steps = 1;
+
+    loop {
+        let current = fingerprint(&hare);
+        if current == tortoise {
+            break;
+        }
+
+        if steps >= limit {
+            return None;
+        }
+
+        if power == length {
+            tortoise = current;
+            power *= 2;
+            length = 0;
+        }
+
+        hare = step(&hare);
+        length += 1;
+        steps += 1;
+    }
+
+    // Then find where the cycle starts by running two states which
+    // are exactly one cycle apart until they meet
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while fingerprint(&tortoise) != fingerprint(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+#[cfg(test)]
+mod test_cycle {
+    use super::*;
+    use rstest::rstest;
+
+    fn next(value: &u64) -> u64 {
+        (value * value + 1) % 255
+    }
+
+    #[rstest(initial, case(0), case(3), case(100), case(254))]
+    fn test_brent_matches_find_cycle(initial: u64) {
+        let expected = find_cycle(initial, next, |v| *v, 1000);
+        let result = brent(initial, next, |v| *v, 1000);
+
+        assert!(expected.is_some());
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // 0, 1, 2, 3, 4, 2, 3, 4, ...
+        let step = |v: &u32| if *v == 4 { 2 } else { v + 1 };
+
+        let expected = Some(Cycle {
+            start: 2,
+            length: 3,
+        });
+
+        assert_eq!(find_cycle(0, step, |v| *v, 100), expected);
+        assert_eq!(brent(0, step, |v| *v, 100), expected);
+    }
+
+    #[test]
+    fn test_limit() {
+        let step = |v: &u32| v + 1;
+
+        assert_eq!(find_cycle(0, step, |v| *v, 100), None);
+        assert_eq!(brent(0, step, |v| *v, 100), None);
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        // the value keeps growing but its last digit repeats
+        let step = |v: &u32| v + 3;
+
+        let expected = Some(Cycle {
+            start: 0,
+            length: 10,
+        });
+
+        assert_eq!(find_cycle(0, step, |v| v % 10, 100), expected);
+        assert_eq!(brent(0, step, |v| v % 10, 100), expected);
+    }
+
+    #[rstest(
+        n,
+        expected,
+        repetitions,
+        case(0, 0, 0),
+        case(1, 1, 0),
+        case(2, 2, 0),
+        case(4, 4, 0),
+        case(5, 2, 1),
+        case(9, 3, 2),
+        case(1_000_000_000, 4, 333_333_332)
+    )]
+    fn test_reduce(n: usize, expected: usize, repetitions: usize) {
+        let cycle = Cycle {
+            start: 2,
+            length: 3,
+        };
+
+        assert_eq!(cycle.reduce(n), expected);
+        assert_eq!(cycle.repetitions(n), repetitions);
+    }
+
+    #[test]
+    fn test_nth() {
+        let step = |v: &u32| if *v == 4 { 2 } else { v + 1 };
+        let cycle = Cycle {
+            start: 2,
+            length: 3,
+        };
+
+        assert_eq!(cycle.nth(0, step, 1), 1);
+        assert_eq!(cycle.nth(0, step, 1_000_000_000_000), 4);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // pattern starts repeating at step 1, moving 5 to the right every 2 steps
+        let values = vec![0, 10, 12, 15];
+        let cycle = Cycle {
+            start: 1,
+            length: 2,
+        };
+
+        assert_eq!(cycle.extrapolate(&values, 0), 0);
+        assert_eq!(cycle.extrapolate(&values, 2), 12);
+        assert_eq!(cycle.extrapolate(&values, 3), 15);
+        assert_eq!(cycle.extrapolate(&values, 4), 17);
+        assert_eq!(cycle.extrapolate(&values, 101), 260);
+    }
+
+    #[test]
+    fn test_detector() {
+        let mut detector = Detector::new();
+
+        assert_eq!(detector.observe("a"), None);
+        assert_eq!(detector.observe("b"), None);
+        assert_eq!(detector.observe("c"), None);
+        assert_eq!(
+            detector.observe("b"),
+            Some(Cycle {
+                start: 1,
+                length: 2
+            })
+        );
+        assert_eq!(detector.steps(), 4);
+    }
+}
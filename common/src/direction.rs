@@ -0,0 +1,317 @@
+// Headings on a grid where x grows to the east and y grows to the south,
+// which is how every map in the puzzles is laid out. Points are plain
+// (x, y) pairs of usize so that each day can convert to and from its own
+// point type. Stepping off the top or left edge of the grid gives None
+// rather than underflowing.
+
+/// One of the four cardinal headings
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// One of the eight compass headings, including the diagonals
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+fn step(x: usize, y: usize, (dx, dy): (i64, i64)) -> Option<(usize, usize)> {
+    let x = (x as i64).checked_add(dx)?;
+    let y = (y as i64).checked_add(dy)?;
+
+    if x < 0 || y < 0 {
+        None
+    } else {
+        Some((x as usize, y as usize))
+    }
+}
+
+fn within(point: Option<(usize, usize)>, width: usize, height: usize) -> Option<(usize, usize)> {
+    point.filter(|&(x, y)| x < width && y < height)
+}
+
+impl Direction {
+    /// Every heading in clockwise order, starting from north
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Every heading in the order the neighbours they lead to are read:
+    /// top to bottom, then left to right
+    pub const READING_ORDER: [Direction; 4] = [
+        Direction::North,
+        Direction::West,
+        Direction::East,
+        Direction::South,
+    ];
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::North => Direction::West,
+            Direction::West => Direction::South,
+            Direction::South => Direction::East,
+            Direction::East => Direction::North,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    /// Change in (x, y) from taking a single step
+    pub fn offset(self) -> (i64, i64) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
+    }
+
+    /// Take a step from the given point. None if the step
+    /// goes past the top or left edge of the grid
+    pub fn step(self, (x, y): (usize, usize)) -> Option<(usize, usize)> {
+        step(x, y, self.offset())
+    }
+
+    /// Take a step, staying inside a grid of the given size
+    pub fn step_within(
+        self,
+        point: (usize, usize),
+        width: usize,
+        height: usize,
+    ) -> Option<(usize, usize)> {
+        within(self.step(point), width, height)
+    }
+}
+
+impl Direction8 {
+    /// Every heading in clockwise order, starting from north
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// Every heading in the order the neighbours they lead to are read
+    pub const READING_ORDER: [Direction8; 8] = [
+        Direction8::NorthWest,
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::West,
+        Direction8::East,
+        Direction8::SouthWest,
+        Direction8::South,
+        Direction8::SouthEast,
+    ];
+
+    fn index(self) -> usize {
+        Direction8::ALL.iter().position(|&d| d == self).unwrap()
+    }
+
+    /// Turn by 45 degrees anticlockwise
+    pub fn turn_left(self) -> Direction8 {
+        Direction8::ALL[(self.index() + 7) % 8]
+    }
+
+    /// Turn by 45 degrees clockwise
+    pub fn turn_right(self) -> Direction8 {
+        Direction8::ALL[(self.index() + 1) % 8]
+    }
+
+    pub fn reverse(self) -> Direction8 {
+        Direction8::ALL[(self.index() + 4) % 8]
+    }
+
+    /// Change in (x, y) from taking a single step
+    pub fn offset(self) -> (i64, i64) {
+        match self {
+            Direction8::North => (0, -1),
+            Direction8::NorthEast => (1, -1),
+            Direction8::East => (1, 0),
+            Direction8::SouthEast => (1, 1),
+            Direction8::South => (0, 1),
+            Direction8::SouthWest => (-1, 1),
+            Direction8::West => (-1, 0),
+            Direction8::NorthWest => (-1, -1),
+        }
+    }
+
+    pub fn step(self, (x, y): (usize, usize)) -> Option<(usize, usize)> {
+        step(x, y, self.offset())
+    }
+
+    pub fn step_within(
+        self,
+        point: (usize, usize),
+        width: usize,
+        height: usize,
+    ) -> Option<(usize, usize)> {
+        within(self.step(point), width, height)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Direction8 {
+        match direction {
+            Direction::North => Direction8::North,
+            Direction::East => Direction8::East,
+            Direction::South => Direction8::South,
+            Direction::West => Direction8::West,
+        }
+    }
+}
+
+/// The four points next to the given point which lie inside
+/// a grid of the given size, in reading order
+pub fn neighbours(
+    point: (usize, usize),
+    width: usize,
+    height: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    Direction::READING_ORDER
+        .iter()
+        .filter_map(move |d| d.step_within(point, width, height))
+}
+
+/// The eight points surrounding the given point which lie inside
+/// a grid of the given size, in reading order
+pub fn neighbours8(
+    point: (usize, usize),
+    width: usize,
+    height: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    Direction8::READING_ORDER
+        .iter()
+        .filter_map(move |d| d.step_within(point, width, height))
+}
+
+#[cfg(test)]
+mod test_direction {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(
+        direction,
+        left,
+        right,
+        reverse,
+        case(Direction::North, Direction::West, Direction::East, Direction::South),
+        case(Direction::East, Direction::North, Direction::South, Direction::West),
+        case(Direction::South, Direction::East, Direction::West, Direction::North),
+        case(Direction::West, Direction::South, Direction::North, Direction::East)
+    )]
+    fn test_turns(direction: Direction, left: Direction, right: Direction, reverse: Direction) {
+        assert_eq!(direction.turn_left(), left);
+        assert_eq!(direction.turn_right(), right);
+        assert_eq!(direction.reverse(), reverse);
+        assert_eq!(direction.turn_left().turn_left(), reverse);
+    }
+
+    #[test]
+    fn test_turns_8() {
+        for &direction in Direction8::ALL.iter() {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.reverse().reverse(), direction);
+
+            let (dx, dy) = direction.offset();
+            assert_eq!(direction.reverse().offset(), (-dx, -dy));
+        }
+
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::NorthWest.turn_right(), Direction8::North);
+
+        for &direction in Direction::ALL.iter() {
+            let turned = Direction8::from(direction).turn_right().turn_right();
+            assert_eq!(turned, Direction8::from(direction.turn_right()));
+        }
+    }
+
+    #[rstest(direction, point, expected,
+        case(Direction::North, (3, 0), None),
+        case(Direction::West, (0, 3), None),
+        case(Direction::North, (3, 1), Some((3, 0))),
+        case(Direction::East, (3, 1), Some((4, 1))),
+        case(Direction::South, (3, 1), Some((3, 2))),
+    )]
+    fn test_step(direction: Direction, point: (usize, usize), expected: Option<(usize, usize)>) {
+        assert_eq!(direction.step(point), expected);
+    }
+
+    #[test]
+    fn test_step_within() {
+        assert_eq!(Direction::East.step_within((2, 0), 3, 3), None);
+        assert_eq!(Direction::South.step_within((0, 2), 3, 3), None);
+        assert_eq!(Direction::South.step_within((0, 1), 3, 3), Some((0, 2)));
+        assert_eq!(Direction8::NorthWest.step_within((0, 1), 3, 3), None);
+        assert_eq!(
+            Direction8::SouthEast.step_within((1, 1), 3, 3),
+            Some((2, 2))
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let result: Vec<_> = neighbours((1, 1), 3, 3).collect();
+        assert_eq!(result, vec![(1, 0), (0, 1), (2, 1), (1, 2)]);
+
+        let result: Vec<_> = neighbours((0, 0), 3, 3).collect();
+        assert_eq!(result, vec![(1, 0), (0, 1)]);
+
+        let result: Vec<_> = neighbours8((1, 1), 3, 3).collect();
+        assert_eq!(
+            result,
+            vec![
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (0, 1),
+                (2, 1),
+                (0, 2),
+                (1, 2),
+                (2, 2)
+            ]
+        );
+
+        // reading order is the same as sorting by row then column
+        let mut sorted = result.clone();
+        sorted.sort_by_key(|&(x, y)| (y, x));
+        assert_eq!(result, sorted);
+
+        let result: Vec<_> = neighbours8((2, 2), 3, 3).collect();
+        assert_eq!(result, vec![(1, 1), (2, 1), (1, 2)]);
+    }
+}
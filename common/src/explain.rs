@@ -0,0 +1,85 @@
+// Structured explanation of how an answer was derived. Each day builds
+// one of these alongside its answer and prints it when run with --explain
+
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub title: String,
+    /// Named values which the answer was derived from
+    pub facts: Vec<(String, String)>,
+    /// Decisions taken along the way, in the order they were taken
+    pub steps: Vec<String>,
+}
+
+impl Explanation {
+    pub fn new(title: &str) -> Explanation {
+        Explanation {
+            title: String::from(title),
+            facts: vec![],
+            steps: vec![],
+        }
+    }
+
+    pub fn fact<T: Display>(mut self, name: &str, value: T) -> Explanation {
+        self.facts.push((String::from(name), value.to_string()));
+        self
+    }
+
+    pub fn step<T: Display>(mut self, step: T) -> Explanation {
+        self.steps.push(step.to_string());
+        self
+    }
+
+    /// Look up the value of a fact by name
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.facts
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        writeln!(f, "{}", self.title)?;
+
+        for (name, value) in &self.facts {
+            writeln!(f, "  {}: {}", name, value)?;
+        }
+
+        for (index, step) in self.steps.iter().enumerate() {
+            writeln!(f, "  {}. {}", index + 1, step)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_explanation {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let explanation = Explanation::new("Sleepiest guard")
+            .fact("guard", "#10")
+            .fact("minutes asleep", 50)
+            .step("guard #10 falls asleep at 00:05")
+            .step("guard #10 wakes up at 00:25");
+
+        let expected = [
+            "Sleepiest guard",
+            "  guard: #10",
+            "  minutes asleep: 50",
+            "  1. guard #10 falls asleep at 00:05",
+            "  2. guard #10 wakes up at 00:25",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(explanation.to_string(), expected);
+        assert_eq!(explanation.get("minutes asleep"), Some("50"));
+        assert_eq!(explanation.get("missing"), None);
+    }
+}
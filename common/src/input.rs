@@ -0,0 +1,93 @@
+// Loading of puzzle inputs. Inputs saved on Windows or copied out of a
+// browser can arrive with CRLF line endings, a byte order mark or extra
+// blank lines at the end, none of which the parsers for each day should
+// have to care about. The normalised input uses "\n" between lines and
+// never ends with a blank line or a line ending.
+
+use std::io::{self, Read};
+
+const BOM: char = '\u{feff}';
+
+/// Whether whitespace at the start and end of each line is part of the input
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Whitespace {
+    /// Lines are kept as they are, as are blank lines at the start.
+    /// Maps such as the tracks on day 13 need their leading spaces
+    Significant,
+    /// Each line is trimmed and blank lines at the start are dropped
+    Insignificant,
+}
+
+pub fn normalise(contents: &str, whitespace: Whitespace) -> String {
+    let contents = contents.strip_prefix(BOM).unwrap_or(contents);
+    let contents = contents.replace("\r\n", "\n").replace('\r', "\n");
+
+    let mut lines: Vec<&str> = match whitespace {
+        Whitespace::Significant => contents.split('\n').collect(),
+        Whitespace::Insignificant => contents
+            .split('\n')
+            .map(str::trim)
+            .skip_while(|line| line.is_empty())
+            .collect(),
+    };
+
+    while let Some(line) = lines.last() {
+        if line.trim().is_empty() {
+            lines.pop();
+        } else {
+            break;
+        }
+    }
+
+    lines.join("\n")
+}
+
+pub fn read<R: Read>(mut reader: R, whitespace: Whitespace) -> io::Result<String> {
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents)?;
+
+    Ok(normalise(&contents, whitespace))
+}
+
+/// Read the whole puzzle input from stdin
+pub fn read_stdin(whitespace: Whitespace) -> io::Result<String> {
+    read(io::stdin(), whitespace)
+}
+
+#[cfg(test)]
+mod test_input {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(
+        contents,
+        expected,
+        case("+1\n-2\n", "+1\n-2"),
+        case("+1\r\n-2\r\n", "+1\n-2"),
+        case("\u{feff}+1\n-2", "+1\n-2"),
+        case("\n\n  +1  \n\n-2\n\n \n", "+1\n\n-2"),
+        case("", ""),
+        case("\r\n\r\n", "")
+    )]
+    fn test_insignificant(contents: &str, expected: &str) {
+        assert_eq!(normalise(contents, Whitespace::Insignificant), expected);
+    }
+
+    #[rstest(
+        contents,
+        expected,
+        case("/->-\\\n|   |\n\\---/\n", "/->-\\\n|   |\n\\---/"),
+        case("  /-\\  \r\n  \\-/\r\n\r\n", "  /-\\  \n  \\-/"),
+        case("\u{feff}\n  |\n  |\n   \n", "\n  |\n  |"),
+        case("|\r|", "|\n|")
+    )]
+    fn test_significant(contents: &str, expected: &str) {
+        assert_eq!(normalise(contents, Whitespace::Significant), expected);
+    }
+
+    #[test]
+    fn test_read() {
+        let contents = read("a\r\nb\r\n".as_bytes(), Whitespace::Insignificant).unwrap();
+        assert_eq!(contents, "a\nb");
+    }
+}
@@ -0,0 +1,298 @@
+// Half open intervals on a line, such as the minutes a guard spends asleep
+// or the time a worker spends on a step. Sets of intervals can be merged
+// and intersected, a sweep over their end points finds how many intervals
+// cover each part of the line, and a histogram counts how many intervals
+// cover each whole slot.
+
+use std::ops::Sub;
+
+/// Every value from start up to, but not including, end
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Ord + Copy> Interval<T> {
+    /// An interval whose end is before its start is empty
+    pub fn new(start: T, end: T) -> Interval<T> {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The part of the line covered by both intervals, if any
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        if overlap.is_empty() {
+            None
+        } else {
+            Some(overlap)
+        }
+    }
+}
+
+impl<T: Ord + Copy + Sub<Output = T> + Default> Interval<T> {
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::default()
+        } else {
+            self.end - self.start
+        }
+    }
+}
+
+/// The fewest disjoint intervals covering the same values, in order.
+/// Intervals which touch end to end are joined together
+pub fn union<T: Ord + Copy>(intervals: &[Interval<T>]) -> Vec<Interval<T>> {
+    let mut sorted: Vec<Interval<T>> = intervals
+        .iter()
+        .filter(|i| !i.is_empty())
+        .copied()
+        .collect();
+    sorted.sort_unstable();
+
+    let mut result: Vec<Interval<T>> = vec![];
+    for interval in sorted {
+        match result.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+            _ => result.push(interval),
+        }
+    }
+    result
+}
+
+/// The values covered by both sets of intervals, as disjoint intervals in order
+pub fn intersection<T: Ord + Copy>(a: &[Interval<T>], b: &[Interval<T>]) -> Vec<Interval<T>> {
+    let (a, b) = (union(a), union(b));
+    let (mut i, mut j) = (0, 0);
+    let mut result = vec![];
+
+    while i < a.len() && j < b.len() {
+        if let Some(overlap) = a[i].intersection(&b[j]) {
+            result.push(overlap);
+        }
+
+        // whichever finishes first cannot overlap anything else
+        if a[i].end < b[j].end {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    result
+}
+
+/// Sweeps over the end points of the intervals, giving each part of the
+/// line covered by at least one interval along with how many cover it
+pub fn coverage<T: Ord + Copy>(intervals: &[Interval<T>]) -> Vec<(Interval<T>, usize)> {
+    let mut events: Vec<(T, bool)> = intervals
+        .iter()
+        .filter(|i| !i.is_empty())
+        .flat_map(|i| vec![(i.start, true), (i.end, false)])
+        .collect();
+    events.sort_unstable();
+
+    let mut result = vec![];
+    let mut count = 0;
+    let mut previous = None;
+
+    for (position, starts) in events {
+        if let Some(start) = previous {
+            if count > 0 && start < position {
+                result.push((Interval::new(start, position), count));
+            }
+        }
+
+        if starts {
+            count += 1;
+        } else {
+            count -= 1;
+        }
+        previous = Some(position);
+    }
+    result
+}
+
+/// Number of intervals covering each whole slot of a range
+#[derive(Debug, PartialEq, Clone)]
+pub struct Histogram {
+    slots: Interval<i64>,
+    counts: Vec<usize>,
+}
+
+impl Histogram {
+    pub fn new(slots: Interval<i64>) -> Histogram {
+        Histogram {
+            slots,
+            counts: vec![0; slots.len() as usize],
+        }
+    }
+
+    /// A histogram with just enough slots to hold every interval
+    pub fn of(intervals: &[Interval<i64>]) -> Histogram {
+        let merged = union(intervals);
+        let slots = match (merged.first(), merged.last()) {
+            (Some(first), Some(last)) => Interval::new(first.start, last.end),
+            _ => Interval::new(0, 0),
+        };
+
+        let mut histogram = Histogram::new(slots);
+        for interval in intervals {
+            histogram.add(interval);
+        }
+        histogram
+    }
+
+    /// Counts the interval in every slot it covers. Any part of
+    /// the interval outside of the histogram is ignored
+    pub fn add(&mut self, interval: &Interval<i64>) {
+        if let Some(overlap) = interval.intersection(&self.slots) {
+            for slot in overlap.start..overlap.end {
+                self.counts[(slot - self.slots.start) as usize] += 1;
+            }
+        }
+    }
+
+    pub fn count(&self, slot: i64) -> usize {
+        if self.slots.contains(slot) {
+            self.counts[(slot - self.slots.start) as usize]
+        } else {
+            0
+        }
+    }
+
+    /// Every slot along with its count, in order
+    pub fn iter(&self) -> impl Iterator<Item = (i64, usize)> + '_ {
+        (self.slots.start..).zip(self.counts.iter().copied())
+    }
+
+    /// The slot covered by the most intervals, preferring the earliest
+    /// on ties, or None if no slot is covered at all
+    pub fn busiest(&self) -> Option<(i64, usize)> {
+        self.iter()
+            .filter(|(_, count)| *count > 0)
+            .fold(None, |best, (slot, count)| match best {
+                Some((_, most)) if most >= count => best,
+                _ => Some((slot, count)),
+            })
+    }
+}
+
+#[cfg(test)]
+mod test_interval {
+    use super::*;
+    use rstest::rstest;
+
+    fn i(start: i64, end: i64) -> Interval<i64> {
+        Interval::new(start, end)
+    }
+
+    #[rstest(
+        a,
+        b,
+        expected,
+        case(i(0, 5), i(3, 8), Some(i(3, 5))),
+        case(i(3, 8), i(0, 5), Some(i(3, 5))),
+        case(i(0, 10), i(2, 4), Some(i(2, 4))),
+        case(i(0, 5), i(5, 8), None),
+        case(i(0, 5), i(6, 8), None)
+    )]
+    fn test_intersection(a: Interval<i64>, b: Interval<i64>, expected: Option<Interval<i64>>) {
+        assert_eq!(a.intersection(&b), expected);
+        assert_eq!(a.overlaps(&b), expected.is_some());
+    }
+
+    #[test]
+    fn test_len() {
+        assert_eq!(i(5, 25).len(), 20);
+        assert_eq!(i(5, 5).len(), 0);
+        assert_eq!(i(5, 2).len(), 0);
+        assert!(i(5, 2).is_empty());
+        assert!(i(5, 25).contains(5));
+        assert!(!i(5, 25).contains(25));
+    }
+
+    #[test]
+    fn test_union() {
+        let intervals = [i(10, 12), i(0, 5), i(3, 7), i(7, 8), i(20, 20)];
+        assert_eq!(union(&intervals), vec![i(0, 8), i(10, 12)]);
+        assert_eq!(union::<i64>(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_intersection_of_sets() {
+        let a = [i(0, 5), i(10, 20)];
+        let b = [i(3, 12), i(15, 16), i(19, 30)];
+        assert_eq!(
+            intersection(&a, &b),
+            vec![i(3, 5), i(10, 12), i(15, 16), i(19, 20)]
+        );
+    }
+
+    #[test]
+    fn test_coverage() {
+        let intervals = [i(0, 4), i(2, 6), i(2, 3), i(8, 9)];
+        assert_eq!(
+            coverage(&intervals),
+            vec![
+                (i(0, 2), 1),
+                (i(2, 3), 3),
+                (i(3, 4), 2),
+                (i(4, 6), 1),
+                (i(8, 9), 1),
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_histogram {
+    use super::*;
+
+    #[test]
+    fn test_of() {
+        let histogram = Histogram::of(&[
+            Interval::new(5, 25),
+            Interval::new(30, 55),
+            Interval::new(24, 29),
+        ]);
+
+        assert_eq!(histogram.count(4), 0);
+        assert_eq!(histogram.count(5), 1);
+        assert_eq!(histogram.count(24), 2);
+        assert_eq!(histogram.count(29), 0);
+        assert_eq!(histogram.busiest(), Some((24, 2)));
+    }
+
+    #[test]
+    fn test_clipped() {
+        let mut histogram = Histogram::new(Interval::new(0, 60));
+        histogram.add(&Interval::new(-10, 2));
+        histogram.add(&Interval::new(58, 70));
+
+        let counts: Vec<(i64, usize)> = histogram.iter().filter(|(_, c)| *c > 0).collect();
+        assert_eq!(counts, vec![(0, 1), (1, 1), (58, 1), (59, 1)]);
+    }
+
+    #[test]
+    fn test_busiest() {
+        let mut histogram = Histogram::new(Interval::new(0, 60));
+        assert_eq!(histogram.busiest(), None);
+
+        // ties go to the earliest slot
+        histogram.add(&Interval::new(10, 12));
+        histogram.add(&Interval::new(3, 4));
+        assert_eq!(histogram.busiest(), Some((3, 1)));
+    }
+}
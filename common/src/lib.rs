@@ -0,0 +1,16 @@
+// Code shared between the solutions for each day
+
+pub mod alloc;
+pub mod cases;
+pub mod cli;
+pub mod cycle;
+pub mod direction;
+pub mod explain;
+pub mod input;
+pub mod interval;
+pub mod logging;
+pub mod prefix;
+pub mod reading;
+pub mod report;
+pub mod search;
+pub mod union_find;
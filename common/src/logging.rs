@@ -0,0 +1,30 @@
+// Structured logging shared by every day. Nothing is logged unless the
+// AOC_LOG environment variable is set. It uses the same syntax as
+// tracing_subscriber's EnvFilter, so traces can be enabled per module:
+//
+//   AOC_LOG=day15=debug cargo run < input.txt
+//   AOC_LOG=day13::map=trace,day12=debug cargo run < input.txt
+//
+// Every event emitted by a solution carries `day`, `part` and `step` fields.
+// Setting AOC_LOG_FORMAT=json writes one JSON object per event instead,
+// which is what the dashboard reads to show live visualisations.
+
+use tracing_subscriber::EnvFilter;
+
+pub const ENV_VAR: &str = "AOC_LOG";
+pub const FORMAT_ENV_VAR: &str = "AOC_LOG_FORMAT";
+
+/// Install the global subscriber. Logs are written to stderr so
+/// they never get mixed up with the answers on stdout.
+pub fn init() {
+    let filter = EnvFilter::try_from_env(ENV_VAR).unwrap_or_else(|_| EnvFilter::new("off"));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+
+    // an error here means a subscriber was already installed
+    let _ = match std::env::var(FORMAT_ENV_VAR).as_deref() {
+        Ok("json") => builder.json().try_init(),
+        _ => builder.try_init(),
+    };
+}
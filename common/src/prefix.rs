@@ -0,0 +1,215 @@
+// Two dimensional prefix sums. A summed-area table answers the sum of any
+// rectangle of a grid in constant time, and a difference array applies an
+// increment to a whole rectangle in constant time, with the values of every
+// cell recovered afterwards by taking its prefix sums.
+// https://en.wikipedia.org/wiki/Summed-area_table
+//
+// Rectangles are given by their top left corner and their size, and must
+// lie within the grid.
+
+use std::ops::{Add, Sub};
+
+/// Values which can be summed up and taken apart again
+pub trait Summable: Copy + Default + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T> Summable for T where T: Copy + Default + Add<Output = T> + Sub<Output = T> {}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SummedArea<T> {
+    width: usize,
+    height: usize,
+    /// Padded with an extra row and column of zeroes at the top and left,
+    /// so that sums[y][x] is the sum of every cell above and to the left
+    /// of (x, y), excluding that row and column
+    sums: Vec<Vec<T>>,
+}
+
+impl<T: Summable> SummedArea<T> {
+    /// Builds the table from rows of equal length
+    pub fn new(grid: &[Vec<T>]) -> SummedArea<T> {
+        let width = grid.first().map_or(0, |row| row.len());
+        SummedArea::from_fn(width, grid.len(), |x, y| grid[y][x])
+    }
+
+    /// Builds the table from the value of each cell, without keeping the grid
+    pub fn from_fn<F>(width: usize, height: usize, mut value: F) -> SummedArea<T>
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        let mut sums = vec![vec![T::default(); width + 1]; height + 1];
+
+        for y in 0..height {
+            for x in 0..width {
+                sums[y + 1][x + 1] = value(x, y) + sums[y][x + 1] + sums[y + 1][x] - sums[y][x];
+            }
+        }
+
+        SummedArea {
+            width,
+            height,
+            sums,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Sum of the rectangle with its top left corner at (x, y)
+    pub fn sum(&self, x: usize, y: usize, width: usize, height: usize) -> T {
+        let (right, bottom) = (x + width, y + height);
+        assert!(
+            right <= self.width && bottom <= self.height,
+            "{}x{} at {},{} is outside of the {}x{} grid",
+            width,
+            height,
+            x,
+            y,
+            self.width,
+            self.height
+        );
+
+        self.sums[bottom][right] + self.sums[y][x] - self.sums[y][right] - self.sums[bottom][x]
+    }
+
+    /// Sum of every cell from the top left corner up to and including (x, y)
+    pub fn up_to(&self, x: usize, y: usize) -> T {
+        self.sum(0, 0, x + 1, y + 1)
+    }
+
+    /// Sum of the whole grid
+    pub fn total(&self) -> T {
+        self.sums[self.height][self.width]
+    }
+}
+
+/// Accumulates increments to rectangles of a grid
+#[derive(Debug, PartialEq, Clone)]
+pub struct Difference<T> {
+    width: usize,
+    height: usize,
+    /// One larger in each direction so that increments touching the
+    /// right or bottom edge have somewhere to be cancelled out
+    deltas: Vec<Vec<T>>,
+}
+
+impl<T: Summable> Difference<T> {
+    pub fn new(width: usize, height: usize) -> Difference<T> {
+        Difference {
+            width,
+            height,
+            deltas: vec![vec![T::default(); width + 1]; height + 1],
+        }
+    }
+
+    /// Adds the value to every cell of the rectangle with its top left corner at (x, y)
+    pub fn add(&mut self, x: usize, y: usize, width: usize, height: usize, value: T) {
+        let (right, bottom) = (x + width, y + height);
+        assert!(
+            right <= self.width && bottom <= self.height,
+            "{}x{} at {},{} is outside of the {}x{} grid",
+            width,
+            height,
+            x,
+            y,
+            self.width,
+            self.height
+        );
+
+        self.deltas[y][x] = self.deltas[y][x] + value;
+        self.deltas[y][right] = self.deltas[y][right] - value;
+        self.deltas[bottom][x] = self.deltas[bottom][x] - value;
+        self.deltas[bottom][right] = self.deltas[bottom][right] + value;
+    }
+
+    /// Value of every cell once all of the increments have been applied
+    pub fn values(&self) -> Vec<Vec<T>> {
+        let table = SummedArea::from_fn(self.width, self.height, |x, y| self.deltas[y][x]);
+
+        (0..self.height)
+            .map(|y| (0..self.width).map(|x| table.up_to(x, y)).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test_summed_area {
+    use super::*;
+    use rstest::rstest;
+
+    fn grid() -> Vec<Vec<i32>> {
+        vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]
+    }
+
+    #[rstest(
+        x,
+        y,
+        width,
+        height,
+        expected,
+        case(0, 0, 1, 1, 1),
+        case(1, 1, 1, 1, 5),
+        case(0, 0, 3, 3, 45),
+        case(1, 1, 2, 2, 28),
+        case(0, 1, 3, 1, 15),
+        case(2, 0, 1, 3, 18),
+        case(1, 1, 0, 0, 0)
+    )]
+    fn test_sum(x: usize, y: usize, width: usize, height: usize, expected: i32) {
+        let table = SummedArea::new(&grid());
+        assert_eq!(table.sum(x, y, width, height), expected);
+    }
+
+    #[test]
+    fn test_up_to() {
+        let table = SummedArea::new(&grid());
+
+        assert_eq!(table.up_to(0, 0), 1);
+        assert_eq!(table.up_to(1, 1), 12);
+        assert_eq!(table.up_to(2, 2), table.total());
+        assert_eq!((table.width(), table.height()), (3, 3));
+    }
+
+    #[test]
+    fn test_empty() {
+        let table: SummedArea<i32> = SummedArea::new(&[]);
+        assert_eq!(table.total(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "2x2 at 2,2 is outside of the 3x3 grid")]
+    fn test_outside() {
+        SummedArea::new(&grid()).sum(2, 2, 2, 2);
+    }
+}
+
+#[cfg(test)]
+mod test_difference {
+    use super::*;
+
+    #[test]
+    fn test_values() {
+        let mut difference = Difference::new(4, 3);
+        difference.add(0, 0, 2, 2, 1);
+        difference.add(1, 1, 3, 2, 2);
+        difference.add(3, 0, 1, 1, -1);
+
+        let expected = vec![vec![1, 1, 0, -1], vec![1, 3, 2, 2], vec![0, 2, 2, 2]];
+        assert_eq!(difference.values(), expected);
+    }
+
+    #[test]
+    fn test_matches_summed_area() {
+        let mut difference = Difference::new(3, 3);
+        difference.add(0, 0, 3, 3, 1);
+        difference.add(1, 0, 2, 3, 1);
+
+        let table = SummedArea::new(&difference.values());
+        assert_eq!(table.total(), 15);
+        assert_eq!(table.sum(1, 1, 2, 2), 8);
+    }
+}
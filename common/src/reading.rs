@@ -0,0 +1,179 @@
+// Reading order: top to bottom, and left to right within each row. Several
+// puzzles use it to decide which of two things on a map goes first, such
+// as which cart moves first or which unit is attacked when there is a tie.
+// Types give their (x, y) position and get a sort key and comparison from
+// it. `Reading` wraps a value so that its own ordering is reading order,
+// which makes it usable as the key of a BTreeMap, and `EarliestFirst`
+// makes a BinaryHeap pop the value which comes first in reading order.
+
+use std::cmp::{Ordering, Reverse};
+use std::hash::{Hash, Hasher};
+
+/// Anything with a position on a grid where y grows to the south
+pub trait ReadingOrder {
+    /// Position as (x, y)
+    fn position(&self) -> (usize, usize);
+
+    /// Sort key which orders by row and then by column
+    fn reading_key(&self) -> (usize, usize) {
+        let (x, y) = self.position();
+        (y, x)
+    }
+
+    fn reading_cmp(&self, other: &Self) -> Ordering {
+        self.reading_key().cmp(&other.reading_key())
+    }
+}
+
+impl ReadingOrder for (usize, usize) {
+    fn position(&self) -> (usize, usize) {
+        *self
+    }
+}
+
+/// Compares two values in reading order. This fits `search::Order`
+pub fn compare<T: ReadingOrder>(a: &T, b: &T) -> Ordering {
+    a.reading_cmp(b)
+}
+
+/// Orders the value it wraps in reading order. Two wrapped values
+/// are equal when they are at the same position
+#[derive(Debug, Clone, Copy)]
+pub struct Reading<T>(pub T);
+
+impl<T: ReadingOrder> PartialEq for Reading<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.reading_key() == other.0.reading_key()
+    }
+}
+
+impl<T: ReadingOrder> Eq for Reading<T> {}
+
+impl<T: ReadingOrder> PartialOrd for Reading<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ReadingOrder> Ord for Reading<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.reading_cmp(&other.0)
+    }
+}
+
+impl<T: ReadingOrder> Hash for Reading<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.reading_key().hash(state);
+    }
+}
+
+/// BinaryHeap pops its largest value first, so the ordering is reversed
+pub type EarliestFirst<T> = Reverse<Reading<T>>;
+
+pub fn earliest_first<T>(value: T) -> EarliestFirst<T> {
+    Reverse(Reading(value))
+}
+
+#[cfg(test)]
+mod test_reading {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::{BTreeMap, BinaryHeap};
+
+    #[rstest(
+        a,
+        b,
+        expected,
+        case((1, 1), (1, 1), Ordering::Equal),
+        case((1, 2), (1, 1), Ordering::Greater),
+        case((2, 0), (4, 1), Ordering::Less),
+        case((2, 3), (4, 3), Ordering::Less),
+        case((9, 3), (7, 3), Ordering::Greater)
+    )]
+    fn test_compare(a: (usize, usize), b: (usize, usize), expected: Ordering) {
+        assert_eq!(compare(&a, &b), expected);
+        assert_eq!(Reading(a).cmp(&Reading(b)), expected);
+    }
+
+    #[test]
+    fn test_btree_map() {
+        let mut map = BTreeMap::new();
+        map.insert(Reading((5, 0)), 'a');
+        map.insert(Reading((0, 1)), 'b');
+        map.insert(Reading((3, 0)), 'c');
+
+        let values: String = map.values().collect();
+        assert_eq!(values, "cab");
+    }
+
+    #[test]
+    fn test_binary_heap() {
+        let mut heap: BinaryHeap<EarliestFirst<(usize, usize)>> = vec![(4, 3), (1, 3), (5, 2)]
+            .into_iter()
+            .map(earliest_first)
+            .collect();
+
+        assert_eq!(heap.pop().map(|Reverse(Reading(p))| p), Some((5, 2)));
+        assert_eq!(heap.pop().map(|Reverse(Reading(p))| p), Some((1, 3)));
+        assert_eq!(heap.pop().map(|Reverse(Reading(p))| p), Some((4, 3)));
+    }
+}
+
+#[cfg(test)]
+mod test_properties {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::{BTreeSet, BinaryHeap};
+
+    fn point() -> impl Strategy<Value = (usize, usize)> {
+        // a small grid so that equal rows and columns come up often
+        (0..8usize, 0..8usize)
+    }
+
+    proptest! {
+        #[test]
+        fn antisymmetric(a in point(), b in point()) {
+            prop_assert_eq!(compare(&a, &b), compare(&b, &a).reverse());
+        }
+
+        #[test]
+        fn transitive(a in point(), b in point(), c in point()) {
+            if compare(&a, &b) != Ordering::Greater && compare(&b, &c) != Ordering::Greater {
+                prop_assert_ne!(compare(&a, &c), Ordering::Greater);
+            }
+        }
+
+        #[test]
+        fn consistent_with_equality(a in point(), b in point()) {
+            prop_assert_eq!(compare(&a, &b) == Ordering::Equal, a == b);
+            prop_assert_eq!(Reading(a) == Reading(b), a == b);
+        }
+
+        #[test]
+        fn rows_come_first(a in point(), b in point()) {
+            if a.1 < b.1 {
+                prop_assert_eq!(compare(&a, &b), Ordering::Less);
+            }
+        }
+
+        #[test]
+        fn collections_agree(points in prop::collection::vec(point(), 0..32)) {
+            let mut sorted = points.clone();
+            sorted.sort_by_key(|p| p.reading_key());
+            sorted.dedup();
+
+            let set: Vec<_> = points.iter().map(|&p| Reading(p)).collect::<BTreeSet<_>>()
+                .into_iter().map(|r| r.0).collect();
+            prop_assert_eq!(&set, &sorted);
+
+            let mut heap: BinaryHeap<_> = points.iter().copied().map(earliest_first).collect();
+            let mut popped = vec![];
+            while let Some(Reverse(Reading(p))) = heap.pop() {
+                if popped.last() != Some(&p) {
+                    popped.push(p);
+                }
+            }
+            prop_assert_eq!(&popped, &sorted);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+// Answers and timings for each part of a solution. When a solution is run
+// with `--report <path>` the report is written to that path as JSON so that
+// other tools, such as the dashboard, can run the solutions and compare
+// their results without having to understand what each day prints.
+
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::alloc::{self, Allocations, Measurement};
+use crate::cli::Options;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Part {
+    pub part: u32,
+    pub answer: Option<String>,
+    /// Time spent solving the part in microseconds
+    pub duration_us: u64,
+    /// Only measured when built with the count-allocations feature
+    #[serde(default)]
+    pub allocations: Option<Allocations>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub day: u32,
+    pub parts: Vec<Part>,
+    /// Peak resident memory of the whole process in kilobytes,
+    /// if the platform makes it available
+    pub peak_rss_kb: Option<u64>,
+}
+
+impl Report {
+    pub fn new(day: u32) -> Report {
+        Report {
+            day,
+            parts: vec![],
+            peak_rss_kb: None,
+        }
+    }
+
+    fn get_part(&mut self, part: u32) -> &mut Part {
+        let index = match self.parts.iter().position(|p| p.part == part) {
+            Some(index) => index,
+            None => {
+                self.parts.push(Part {
+                    part,
+                    answer: None,
+                    duration_us: 0,
+                    allocations: None,
+                });
+                self.parts.len() - 1
+            }
+        };
+        &mut self.parts[index]
+    }
+
+    /// Run the code which solves a part, recording how long it took and,
+    /// if they are being counted, the allocations it made.
+    /// Timing the same part more than once adds up the measurements
+    pub fn time<T, F>(&mut self, part: u32, solve: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        let measurement = Measurement::start();
+        let start = Instant::now();
+        let result = solve();
+        let elapsed = start.elapsed().as_micros() as u64;
+        let allocations = measurement.finish();
+
+        let part = self.get_part(part);
+        part.duration_us += elapsed;
+
+        if alloc::enabled() {
+            part.allocations = Some(match part.allocations {
+                Some(previous) => previous.merge(allocations),
+                None => allocations,
+            });
+        }
+        result
+    }
+
+    pub fn answer<T: Display>(&mut self, part: u32, answer: T) {
+        self.get_part(part).answer = Some(answer.to_string());
+    }
+
+    /// Write the report to the path given on the command line, if any
+    pub fn write(&mut self, options: &Options) -> io::Result<()> {
+        if let Some(path) = &options.report {
+            self.peak_rss_kb = peak_rss_kb();
+            fs::write(path, serde_json::to_string_pretty(self)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Peak resident set size of the current process. Only available on Linux
+fn peak_rss_kb() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+
+    status
+        .lines()
+        .find(|line| line.starts_with("VmHWM:"))?
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod test_report {
+    use super::*;
+
+    #[test]
+    fn test_parts() {
+        let mut report = Report::new(1);
+
+        let result = report.time(1, || 2 + 2);
+        report.answer(1, result);
+        report.answer(2, "abc");
+        report.time(1, || ());
+
+        let answers: Vec<_> = report
+            .parts
+            .iter()
+            .map(|p| (p.part, p.answer.as_deref()))
+            .collect();
+        assert_eq!(answers, vec![(1, Some("4")), (2, Some("abc"))]);
+        assert_eq!(report.parts[0].allocations.is_some(), alloc::enabled());
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<(), serde_json::Error> {
+        let mut report = Report::new(15);
+        report.answer(1, 221840);
+
+        let contents = serde_json::to_string(&report)?;
+        let result: Report = serde_json::from_str(&contents)?;
+
+        assert_eq!(result, report);
+        Ok(())
+    }
+}
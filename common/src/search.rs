@@ -0,0 +1,484 @@
+// Graph searches over implicit graphs. The graph is never built up front,
+// instead every search is given a function which returns the neighbours
+// of a node when it is visited.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Tie-break order used whenever several nodes are equally good.
+/// For most grid puzzles this is reading order.
+pub type Order<N> = fn(&N, &N) -> Ordering;
+
+/// The result of running a search from one or more sources.
+#[derive(Debug, Clone)]
+pub struct Search<N, C = u32> {
+    costs: HashMap<N, C>,
+    parents: HashMap<N, N>,
+    origins: HashMap<N, Option<usize>>,
+    order: Order<N>,
+}
+
+impl<N, C> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord,
+{
+    fn new(order: Order<N>) -> Search<N, C> {
+        Search {
+            costs: HashMap::new(),
+            parents: HashMap::new(),
+            origins: HashMap::new(),
+            order,
+        }
+    }
+
+    /// Cost of the cheapest path from a source to the given node.
+    /// Returns None if the node could not be reached.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.costs.contains_key(node)
+    }
+
+    /// The node visited right before the given node on its cheapest path.
+    /// When several cheapest paths exist, the parent which comes first
+    /// in the tie-break order is chosen.
+    pub fn parent(&self, node: &N) -> Option<&N> {
+        self.parents.get(node)
+    }
+
+    /// Index of the source which is uniquely closest to the given node.
+    /// Returns None if the node was not reached or if it is equally
+    /// close to more than one source.
+    pub fn origin(&self, node: &N) -> Option<usize> {
+        self.origins.get(node).copied().flatten()
+    }
+
+    /// Iterate over every node that was reached along with its cost
+    pub fn iter(&self) -> impl Iterator<Item = (&N, &C)> {
+        self.costs.iter()
+    }
+
+    /// Path from the given node back to the source it was reached from.
+    /// Both the given node and the source are included.
+    pub fn path_from(&self, node: &N) -> Option<Vec<N>> {
+        if !self.contains(node) {
+            return None;
+        }
+
+        let mut result = vec![node.clone()];
+        let mut current = node;
+
+        while let Some(parent) = self.parents.get(current) {
+            result.push(parent.clone());
+            current = parent;
+        }
+
+        Some(result)
+    }
+
+    /// Path from the source to the given node, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut result = self.path_from(node)?;
+        result.reverse();
+        Some(result)
+    }
+
+    /// Find the candidate which is cheapest to reach. Ties are broken
+    /// using the tie-break order of the search. Candidates which
+    /// were never reached are ignored.
+    pub fn nearest<I: IntoIterator<Item = N>>(&self, candidates: I) -> Option<N> {
+        let mut result: Option<(C, N)> = None;
+
+        for candidate in candidates {
+            if let Some(cost) = self.distance(&candidate) {
+                let better = match &result {
+                    None => true,
+                    Some((best, node)) => {
+                        cost < *best || (cost == *best && (self.order)(&candidate, node).is_lt())
+                    }
+                };
+
+                if better {
+                    result = Some((cost, candidate));
+                }
+            }
+        }
+
+        result.map(|(_, node)| node)
+    }
+
+    /// Record that `node` can be reached from `parent` with the same cost
+    /// it is already known to have.
+    fn merge(&mut self, node: &N, parent: &N) {
+        if let Some(current) = self.parents.get(node) {
+            if (self.order)(parent, current).is_lt() {
+                self.parents.insert(node.clone(), parent.clone());
+            }
+        }
+
+        let origin = self.origins.get(parent).copied().flatten();
+        if self.origins.get(node).copied().flatten() != origin {
+            self.origins.insert(node.clone(), None);
+        }
+    }
+
+    fn insert(&mut self, node: N, cost: C, parent: Option<&N>) {
+        if let Some(parent) = parent {
+            let origin = self.origins.get(parent).copied().flatten();
+            self.origins.insert(node.clone(), origin);
+            self.parents.insert(node.clone(), parent.clone());
+        }
+        self.costs.insert(node, cost);
+    }
+}
+
+/// Breadth first search where every step has a cost of 1
+pub fn bfs<N, F, I>(start: N, neighbours: F, order: Order<N>) -> Search<N>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    multi_source_bfs(std::iter::once(start), neighbours, order)
+}
+
+/// Breadth first search starting from all the given sources at once.
+/// Every node reached remembers which source is closest to it, see
+/// `Search::origin`.
+pub fn multi_source_bfs<N, S, F, I>(sources: S, mut neighbours: F, order: Order<N>) -> Search<N>
+where
+    N: Clone + Eq + Hash,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new(order);
+    let mut queue = VecDeque::new();
+
+    for (index, source) in sources.into_iter().enumerate() {
+        if search.contains(&source) {
+            // the same node was given as a source twice
+            search.origins.insert(source, None);
+            continue;
+        }
+
+        search.origins.insert(source.clone(), Some(index));
+        search.insert(source.clone(), 0, None);
+        queue.push_back(source);
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let cost = search.costs[&node] + 1;
+
+        for next in neighbours(&node) {
+            match search.distance(&next) {
+                None => {
+                    search.insert(next.clone(), cost, Some(&node));
+                    queue.push_back(next);
+                }
+                Some(existing) if existing == cost => search.merge(&next, &node),
+                Some(_) => {}
+            }
+        }
+    }
+
+    search
+}
+
+/// Entry stored in the priority queue used by dijkstra and A*.
+/// Lower priorities are popped first, ties are broken by the
+/// tie-break order of the nodes.
+struct Entry<N, C> {
+    priority: C,
+    node: N,
+    order: Order<N>,
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap is a max heap so the comparison is reversed
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| (self.order)(&other.node, &self.node))
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+/// Dijkstra's shortest path algorithm. The neighbour function returns
+/// each neighbour along with the cost of stepping to it.
+pub fn dijkstra<N, C, F, I>(start: N, neighbours: F, order: Order<N>) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (search, _) = best_first(start, |_| false, neighbours, |_| C::default(), order);
+    search
+}
+
+/// A* search from start until a node satisfying `is_goal` is found.
+/// The heuristic must never overestimate the remaining cost for the
+/// result to be the cheapest path.
+/// Returns the path (start and goal included) and its total cost.
+pub fn astar<N, C, G, F, I, H>(
+    start: N,
+    is_goal: G,
+    neighbours: F,
+    heuristic: H,
+    order: Order<N>,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    G: FnMut(&N) -> bool,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+{
+    let (search, goal) = best_first(start, is_goal, neighbours, heuristic, order);
+    let goal = goal?;
+    let cost = search.distance(&goal)?;
+
+    Some((search.path_to(&goal)?, cost))
+}
+
+/// Shared implementation of dijkstra and A*. Dijkstra is just A*
+/// with a heuristic that is always zero and a goal that never matches.
+fn best_first<N, C, G, F, I, H>(
+    start: N,
+    mut is_goal: G,
+    mut neighbours: F,
+    mut heuristic: H,
+    order: Order<N>,
+) -> (Search<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    G: FnMut(&N) -> bool,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+{
+    let mut search = Search::new(order);
+    let mut settled = HashSet::new();
+    let mut heap = BinaryHeap::new();
+
+    search.origins.insert(start.clone(), Some(0));
+    search.insert(start.clone(), C::default(), None);
+    heap.push(Entry {
+        priority: heuristic(&start),
+        node: start,
+        order,
+    });
+
+    while let Some(Entry { node, .. }) = heap.pop() {
+        if !settled.insert(node.clone()) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return (search, Some(node));
+        }
+
+        let cost = search.costs[&node];
+
+        for (next, step) in neighbours(&node) {
+            if settled.contains(&next) {
+                continue;
+            }
+
+            let next_cost = cost + step;
+
+            match search.distance(&next) {
+                Some(existing) if existing < next_cost => continue,
+                Some(existing) if existing == next_cost => {
+                    search.merge(&next, &node);
+                    continue;
+                }
+                _ => {}
+            }
+
+            search.insert(next.clone(), next_cost, Some(&node));
+            heap.push(Entry {
+                priority: next_cost + heuristic(&next),
+                node: next,
+                order,
+            });
+        }
+    }
+
+    (search, None)
+}
+
+#[cfg(test)]
+mod test_search {
+    use super::*;
+    use rstest::rstest;
+
+    type Point = (usize, usize);
+
+    fn reading_order(a: &Point, b: &Point) -> Ordering {
+        (a.1, a.0).cmp(&(b.1, b.0))
+    }
+
+    fn parse(map: &[&str]) -> Vec<Vec<char>> {
+        map.iter().map(|row| row.chars().collect()).collect()
+    }
+
+    /// Open neighbours of a point in reading order
+    fn open(grid: &[Vec<char>], point: &Point) -> Vec<Point> {
+        let (x, y) = *point;
+        let mut result = vec![];
+
+        if y > 0 {
+            result.push((x, y - 1));
+        }
+        if x > 0 {
+            result.push((x - 1, y));
+        }
+        result.push((x + 1, y));
+        result.push((x, y + 1));
+
+        result
+            .into_iter()
+            .filter(|(x, y)| grid.get(*y).and_then(|row| row.get(*x)) == Some(&'.'))
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let grid = parse(&["#######", "#...#.#", "#.#...#", "#######"]);
+
+        let search = bfs((1, 1), |p| open(&grid, p), reading_order);
+
+        assert_eq!(search.distance(&(1, 1)), Some(0));
+        assert_eq!(search.distance(&(3, 1)), Some(2));
+        assert_eq!(search.distance(&(5, 1)), Some(6));
+        assert_eq!(search.distance(&(0, 0)), None);
+        assert_eq!(search.iter().count(), 8);
+    }
+
+    #[test]
+    fn test_bfs_path_uses_reading_order() {
+        let grid = parse(&["#####", "#...#", "#...#", "#...#", "#####"]);
+
+        let search = bfs((3, 3), |p| open(&grid, p), reading_order);
+
+        // Both (2, 1) and (1, 2) are 1 step closer to the source
+        // but (2, 1) comes first in reading order
+        let path = search.path_from(&(1, 1));
+        assert_eq!(path, Some(vec![(1, 1), (2, 1), (3, 1), (3, 2), (3, 3)]));
+
+        let path = search.path_to(&(1, 1));
+        assert_eq!(path, Some(vec![(3, 3), (3, 2), (3, 1), (2, 1), (1, 1)]));
+
+        assert_eq!(search.path_to(&(0, 0)), None);
+    }
+
+    #[rstest(candidates, expected,
+        case(vec![(3, 1), (1, 3)], Some((3, 1))),
+        case(vec![(1, 3), (3, 1)], Some((3, 1))),
+        case(vec![(1, 1), (2, 2)], Some((2, 2))),
+        case(vec![(0, 0)], None),
+        case(vec![], None),
+    )]
+    fn test_nearest(candidates: Vec<Point>, expected: Option<Point>) {
+        let grid = parse(&["#####", "#...#", "#...#", "#...#", "#####"]);
+
+        let search = bfs((3, 3), |p| open(&grid, p), reading_order);
+
+        assert_eq!(search.nearest(candidates), expected);
+    }
+
+    #[test]
+    fn test_multi_source_bfs() {
+        let grid = parse(&["#######", "#.....#", "#.....#", "#######"]);
+
+        let search = multi_source_bfs(vec![(1, 1), (5, 1)], |p| open(&grid, p), reading_order);
+
+        assert_eq!(search.origin(&(1, 1)), Some(0));
+        assert_eq!(search.origin(&(2, 2)), Some(0));
+        assert_eq!(search.origin(&(5, 2)), Some(1));
+        assert_eq!(search.origin(&(4, 2)), Some(1));
+
+        // equally distant from both sources
+        assert_eq!(search.origin(&(3, 2)), None);
+        assert_eq!(search.origin(&(3, 1)), None);
+        assert_eq!(search.distance(&(3, 1)), Some(2));
+
+        assert_eq!(search.origin(&(0, 0)), None);
+    }
+
+    fn weighted(grid: &[Vec<char>], point: &Point) -> Vec<(Point, u32)> {
+        // moving down is expensive
+        open(grid, point)
+            .into_iter()
+            .map(|next| (next, if next.1 > point.1 { 5 } else { 1 }))
+            .collect()
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let grid = parse(&["#####", "#...#", "#.#.#", "#...#", "#####"]);
+
+        let search = dijkstra((1, 1), |p| weighted(&grid, p), reading_order);
+
+        assert_eq!(search.distance(&(1, 1)), Some(0));
+        assert_eq!(search.distance(&(3, 1)), Some(2));
+        assert_eq!(search.distance(&(1, 3)), Some(10));
+        assert_eq!(search.distance(&(3, 3)), Some(12));
+
+        // both ways around the wall cost the same so the
+        // parent is picked in reading order
+        assert_eq!(search.parent(&(3, 3)), Some(&(3, 2)));
+        assert_eq!(search.parent(&(2, 3)), Some(&(1, 3)));
+    }
+
+    #[test]
+    fn test_astar() {
+        let grid = parse(&["#######", "#.....#", "#.###.#", "#.....#", "#######"]);
+
+        let manhattan =
+            |p: &Point| (p.0 as i32 - 5).unsigned_abs() + (p.1 as i32 - 3).unsigned_abs();
+
+        let result = astar(
+            (1, 1),
+            |p| *p == (5, 3),
+            |p| weighted(&grid, p),
+            manhattan,
+            reading_order,
+        );
+
+        let expected = vec![(1, 1), (2, 1), (3, 1), (4, 1), (5, 1), (5, 2), (5, 3)];
+        assert_eq!(result, Some((expected, 14)));
+
+        let result = astar(
+            (1, 1),
+            |p| *p == (0, 0),
+            |p| weighted(&grid, p),
+            |_| 0,
+            reading_order,
+        );
+        assert_eq!(result, None);
+    }
+}
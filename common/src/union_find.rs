@@ -0,0 +1,81 @@
+// Keeps track of which items have been joined into the same group, for
+// finding the connected parts of a graph one edge at a time. Each group
+// is a tree of items pointing towards its root, and paths are halved on
+// every lookup so that the trees stay shallow.
+// https://en.wikipedia.org/wiki/Disjoint-set_data_structure
+
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+}
+
+impl UnionFind {
+    /// Items numbered from 0 up to len, each in a group of its own
+    pub fn new(len: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..len).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The lowest numbered item in the group of the given item
+    pub fn find(&mut self, mut item: usize) -> usize {
+        while self.parents[item] != item {
+            self.parents[item] = self.parents[self.parents[item]];
+            item = self.parents[item];
+        }
+        item
+    }
+
+    /// Joins the groups of the two items, returning false if they were
+    /// already in the same group
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        // the lowest numbered item stays the root, so groups can be
+        // listed in the order of their first item
+        self.parents[a.max(b)] = a.min(b);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test_union_find {
+    use super::*;
+
+    #[test]
+    fn test_separate() {
+        let mut groups = UnionFind::new(3);
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!((groups.find(0), groups.find(1), groups.find(2)), (0, 1, 2));
+    }
+
+    #[test]
+    fn test_union() {
+        let mut groups = UnionFind::new(6);
+
+        assert!(groups.union(4, 2));
+        assert!(groups.union(5, 4));
+        assert!(groups.union(3, 1));
+        assert!(!groups.union(2, 5));
+
+        let roots: Vec<usize> = (0..6).map(|i| groups.find(i)).collect();
+        assert_eq!(roots, vec![0, 1, 2, 1, 2, 2]);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(UnionFind::new(0).is_empty());
+    }
+}
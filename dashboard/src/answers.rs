@@ -0,0 +1,141 @@
+// Answers recorded from earlier runs. Every new answer is compared against
+// them so that a change which breaks a solution stands out straight away.
+// Stored as JSON mapping each day to the answer for each of its parts.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use common::report::Report;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    days: BTreeMap<u32, BTreeMap<u32, String>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    Match,
+    /// The answer differs from the one recorded, which is given
+    Regression(String),
+    Unrecorded,
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(io::Error),
+    Json(serde_json::Error),
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            AnswersError::Io(err) => write!(f, "{}", err),
+            AnswersError::Json(err) => write!(f, "Invalid answers file: {}", err),
+        }
+    }
+}
+
+impl Error for AnswersError {}
+
+impl From<io::Error> for AnswersError {
+    fn from(err: io::Error) -> Self {
+        AnswersError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for AnswersError {
+    fn from(err: serde_json::Error) -> Self {
+        AnswersError::Json(err)
+    }
+}
+
+impl Answers {
+    /// Load recorded answers. A missing file means nothing has been recorded yet
+    pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Answers {
+                days: serde_json::from_str(&contents)?,
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        let mut contents = serde_json::to_string_pretty(&self.days)?;
+        contents.push('\n');
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.days.get(&day)?.get(&part).map(String::as_str)
+    }
+
+    pub fn check(&self, day: u32, part: u32, answer: &str) -> Check {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Check::Match,
+            Some(expected) => Check::Regression(String::from(expected)),
+            None => Check::Unrecorded,
+        }
+    }
+
+    /// Record every answer in the report, replacing any recorded before
+    pub fn record(&mut self, report: &Report) {
+        let parts = self.days.entry(report.day).or_default();
+
+        for part in &report.parts {
+            if let Some(answer) = &part.answer {
+                parts.insert(part.part, answer.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_answers {
+    use super::*;
+
+    fn get_report() -> Report {
+        let mut report = Report::new(1);
+        report.answer(1, 525);
+        report.answer(2, 75749);
+        report
+    }
+
+    #[test]
+    fn test_check() {
+        let mut answers = Answers::default();
+        assert_eq!(answers.check(1, 1, "525"), Check::Unrecorded);
+
+        answers.record(&get_report());
+
+        assert_eq!(answers.check(1, 1, "525"), Check::Match);
+        assert_eq!(
+            answers.check(1, 2, "75750"),
+            Check::Regression(String::from("75749"))
+        );
+        assert_eq!(answers.check(2, 1, "525"), Check::Unrecorded);
+    }
+
+    #[test]
+    fn test_save_and_load() -> Result<(), AnswersError> {
+        let path = std::env::temp_dir().join(format!("answers-{}.json", std::process::id()));
+
+        assert_eq!(Answers::load(&path)?, Answers::default());
+
+        let mut answers = Answers::default();
+        answers.record(&get_report());
+        answers.save(&path)?;
+
+        let result = Answers::load(&path);
+        fs::remove_file(&path)?;
+
+        assert_eq!(result?, answers);
+        Ok(())
+    }
+}
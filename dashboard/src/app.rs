@@ -0,0 +1,293 @@
+// State of the dashboard and how it reacts to keys being pressed and to
+// events coming back from the solutions it is running
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+use ratatui::crossterm::event::KeyCode;
+
+use crate::answers::{Answers, Check};
+use crate::day::Day;
+use crate::runner::{self, Event, Run, RunOptions};
+use crate::visualisation::Visualisation;
+
+#[derive(Debug)]
+pub enum Status {
+    Pending,
+    Running,
+    Done(Run),
+    Failed(String),
+}
+
+pub struct App {
+    pub days: Vec<Day>,
+    pub status: BTreeMap<u32, Status>,
+    pub answers: Answers,
+    pub answers_path: PathBuf,
+    /// Number of days run at the same time. 1 runs them in order
+    pub jobs: usize,
+    pub max_jobs: usize,
+    /// Whether days are built with the counting allocator
+    pub count_allocations: bool,
+    /// Index into `days`
+    pub selected: usize,
+    pub visualisation: Option<Visualisation>,
+    pub message: Option<String>,
+    pub quit: bool,
+    events: Sender<Event>,
+}
+
+impl App {
+    pub fn new(
+        days: Vec<Day>,
+        answers: Answers,
+        answers_path: PathBuf,
+        max_jobs: usize,
+        events: Sender<Event>,
+    ) -> App {
+        let status = days.iter().map(|d| (d.number, Status::Pending)).collect();
+
+        App {
+            days,
+            status,
+            answers,
+            answers_path,
+            jobs: 1,
+            max_jobs: max_jobs.max(1),
+            count_allocations: false,
+            selected: 0,
+            visualisation: None,
+            message: None,
+            quit: false,
+            events,
+        }
+    }
+
+    pub fn selected_day(&self) -> Option<&Day> {
+        self.days.get(self.selected)
+    }
+
+    pub fn handle(&mut self, event: Event) {
+        match event {
+            Event::Started(day) => {
+                self.status.insert(day, Status::Running);
+            }
+            Event::Finished(day, Ok(run)) => {
+                self.status.insert(day, Status::Done(run));
+            }
+            Event::Finished(day, Err(err)) => {
+                self.status.insert(day, Status::Failed(err.to_string()));
+            }
+            Event::Frame(day, frame) => {
+                if let Some(visualisation) = &mut self.visualisation {
+                    if visualisation.day == day {
+                        visualisation.push(frame);
+                    }
+                }
+            }
+            Event::Visualised(day, Err(err)) => {
+                self.message = Some(format!("Day {} failed: {}", day, err));
+            }
+            Event::Visualised(_, Ok(())) => {}
+        }
+    }
+
+    /// Move the animation on, called at a fixed interval
+    pub fn tick(&mut self) {
+        if let Some(visualisation) = &mut self.visualisation {
+            visualisation.tick();
+        }
+    }
+
+    pub fn key(&mut self, code: KeyCode) {
+        if let Some(visualisation) = &mut self.visualisation {
+            match code {
+                KeyCode::Esc | KeyCode::Char('v') => self.visualisation = None,
+                KeyCode::Char(' ') => visualisation.toggle(),
+                KeyCode::Left => visualisation.back(),
+                KeyCode::Right => visualisation.forward(),
+                KeyCode::Char('q') => self.quit = true,
+                _ => {}
+            }
+            return;
+        }
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if self.selected + 1 < self.days.len() => {
+                self.selected += 1;
+            }
+            KeyCode::Char('p') => {
+                self.jobs = if self.jobs == 1 { self.max_jobs } else { 1 };
+            }
+            KeyCode::Char('m') => self.count_allocations = !self.count_allocations,
+            KeyCode::Char('a') => self.run(self.days.clone()),
+            KeyCode::Enter | KeyCode::Char('r') => {
+                if let Some(day) = self.selected_day() {
+                    self.run(vec![day.clone()]);
+                }
+            }
+            KeyCode::Char('v') => {
+                if let Some(day) = self.selected_day().cloned() {
+                    self.visualisation = Some(Visualisation::new(day.number));
+                    runner::visualise(day, self.events.clone());
+                }
+            }
+            KeyCode::Char('s') => self.record(),
+            _ => {}
+        }
+    }
+
+    fn run(&mut self, days: Vec<Day>) {
+        for day in &days {
+            self.status.insert(day.number, Status::Pending);
+        }
+        let options = RunOptions {
+            count_allocations: self.count_allocations,
+            ..RunOptions::default()
+        };
+        runner::run_all(days, self.jobs, options, self.events.clone());
+    }
+
+    /// Record the answers from every finished run as the expected answers
+    fn record(&mut self) {
+        for status in self.status.values() {
+            if let Status::Done(run) = status {
+                self.answers.record(&run.report);
+            }
+        }
+
+        self.message = Some(match self.answers.save(&self.answers_path) {
+            Ok(()) => format!("Answers recorded to {}", self.answers_path.display()),
+            Err(err) => format!("Unable to record answers: {}", err),
+        });
+    }
+
+    /// Compare the answer to a part against the one recorded for it
+    pub fn check(&self, day: u32, part: u32, answer: &str) -> Check {
+        self.answers.check(day, part, answer)
+    }
+
+    /// Number of parts whose answer has changed, along with the number of days
+    /// which failed to run
+    pub fn problems(&self) -> (usize, usize) {
+        let mut regressions = 0;
+        let mut failures = 0;
+
+        for (day, status) in &self.status {
+            match status {
+                Status::Done(run) => {
+                    for part in &run.report.parts {
+                        let answer = part.answer.as_deref().unwrap_or("");
+                        if let Check::Regression(_) = self.check(*day, part.part, answer) {
+                            regressions += 1;
+                        }
+                    }
+                }
+                Status::Failed(_) => failures += 1,
+                _ => {}
+            }
+        }
+
+        (regressions, failures)
+    }
+}
+
+#[cfg(test)]
+mod test_app {
+    use super::*;
+    use crate::runner::RunError;
+    use crate::visualisation::Frame;
+    use common::report::Report;
+    use std::sync::mpsc;
+
+    fn get_app() -> App {
+        let days = (1..=3)
+            .map(|number| Day {
+                number,
+                path: PathBuf::from(format!("day{:02}", number)),
+            })
+            .collect();
+
+        let mut answers = Answers::default();
+        let mut report = Report::new(1);
+        report.answer(1, 525);
+        report.answer(2, 75749);
+        answers.record(&report);
+
+        let (sender, _) = mpsc::channel();
+        App::new(days, answers, PathBuf::from("answers.json"), 4, sender)
+    }
+
+    fn finished(day: u32, answers: &[&str]) -> Event {
+        let mut report = Report::new(day);
+        for (index, answer) in answers.iter().enumerate() {
+            report.answer(index as u32 + 1, answer);
+        }
+        Event::Finished(day, Ok(Run { report }))
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut app = get_app();
+
+        app.key(KeyCode::Up);
+        assert_eq!(app.selected, 0);
+
+        for _ in 0..5 {
+            app.key(KeyCode::Down);
+        }
+        assert_eq!(app.selected_day().map(|d| d.number), Some(3));
+
+        app.key(KeyCode::Char('p'));
+        assert_eq!(app.jobs, 4);
+        app.key(KeyCode::Char('p'));
+        assert_eq!(app.jobs, 1);
+
+        app.key(KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn test_problems() {
+        let mut app = get_app();
+        assert_eq!(app.problems(), (0, 0));
+
+        app.handle(Event::Started(1));
+        assert!(matches!(app.status[&1], Status::Running));
+
+        app.handle(finished(1, &["525", "1"]));
+        app.handle(finished(2, &["abc"]));
+        app.handle(Event::Finished(
+            3,
+            Err(RunError::Failed(String::from("error: could not compile"))),
+        ));
+
+        assert_eq!(app.problems(), (1, 1));
+    }
+
+    #[test]
+    fn test_frames() {
+        let mut app = get_app();
+        let frame = Frame {
+            title: String::from("round 1"),
+            map: String::from("#E.G#"),
+        };
+
+        // frames are ignored unless the day is being visualised
+        app.handle(Event::Frame(1, frame.clone()));
+
+        app.visualisation = Some(Visualisation::new(1));
+        app.handle(Event::Frame(2, frame.clone()));
+        app.handle(Event::Frame(1, frame.clone()));
+
+        let frames = app.visualisation.as_ref().map(|v| v.frames.clone());
+        assert_eq!(frames, Some(vec![frame]));
+
+        app.key(KeyCode::Esc);
+        assert!(app.visualisation.is_none());
+        assert!(!app.quit);
+    }
+}
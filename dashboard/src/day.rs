@@ -0,0 +1,92 @@
+// The solution for each day lives in its own crate in a directory named
+// dayNN at the root of the repository, next to the input it was written for
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Day {
+    pub number: u32,
+    pub path: PathBuf,
+}
+
+impl Day {
+    /// Puzzle input for the day. Some days have their input written
+    /// into the solution and do not read anything from stdin
+    pub fn input(&self) -> Option<PathBuf> {
+        let path = self.path.join("input.txt");
+        if path.is_file() {
+            Some(path)
+        } else {
+            None
+        }
+    }
+}
+
+fn parse_day(name: &str) -> Option<u32> {
+    let number = name.strip_prefix("day")?;
+
+    if number.len() == 2 && number.chars().all(|c| c.is_ascii_digit()) {
+        number.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Find every day in the repository, in order
+pub fn discover(root: &Path) -> io::Result<Vec<Day>> {
+    let mut days = vec![];
+
+    for entry in fs::read_dir(root)? {
+        let path = entry?.path();
+
+        if !path.join("Cargo.toml").is_file() {
+            continue;
+        }
+
+        let number = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(parse_day);
+        if let Some(number) = number {
+            days.push(Day { number, path });
+        }
+    }
+
+    days.sort_by_key(|d| d.number);
+    Ok(days)
+}
+
+#[cfg(test)]
+mod test_day {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(
+        name,
+        expected,
+        case("day01", Some(1)),
+        case("day15", Some(15)),
+        case("day1", None),
+        case("day001", None),
+        case("dayab", None),
+        case("common", None)
+    )]
+    fn test_parse_day(name: &str, expected: Option<u32>) {
+        assert_eq!(parse_day(name), expected);
+    }
+
+    #[test]
+    fn test_discover() -> io::Result<()> {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let days = discover(&root)?;
+
+        assert_eq!(days[0].number, 1);
+        assert!(days[0].input().is_some());
+        assert!(days.windows(2).all(|w| w[0].number < w[1].number));
+        assert!(days.iter().all(|d| d.path.join("src").is_dir()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,429 @@
+// Full screen dashboard which runs the solution for every day and shows
+// their answers, timings and memory use. Answers are compared against the
+// ones recorded in answers.json so that regressions stand out.
+//
+//   cargo run                 interactive dashboard
+//   cargo run -- --check      run every day and report any regressions
+//   cargo run -- --record     run every day and record their answers
+//   cargo run -- --watch 9    run day 9 again whenever it changes
+//   cargo run -- --submit 9 --part 1
+//                             solve day 9 part 1 and submit the answer
+//
+// Adding --allocations builds every day with the counting allocator so
+// that the peak heap and number of allocations of each part are shown.
+// When watching, --part can be given to only solve some of the parts.
+// Submitting needs the session cookie in AOC_SESSION, and --endpoint
+// points it somewhere other than the puzzle website.
+
+mod answers;
+mod app;
+mod day;
+mod runner;
+mod submit;
+mod ui;
+mod visualisation;
+mod watch;
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use common::cli::OptionsError;
+use ratatui::crossterm::event::{self, Event as TermEvent, KeyEventKind};
+
+use answers::{Answers, Check};
+use app::{App, Status};
+use day::Day;
+use runner::{Event, RunOptions};
+use submit::{Rejected, SubmitError, Submitter};
+
+/// How often the visualisation moves on to its next frame
+const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, PartialEq)]
+enum Mode {
+    Interactive,
+    Check,
+    Record,
+    Watch(u32),
+    Submit(u32),
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    root: PathBuf,
+    answers: Option<PathBuf>,
+    jobs: usize,
+    count_allocations: bool,
+    parts: Vec<u32>,
+    endpoint: String,
+    mode: Mode,
+}
+
+impl Options {
+    fn parse<I, S>(args: I) -> Result<Options, OptionsError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut options = Options {
+            // the dashboard lives next to the days it runs
+            root: Path::new(env!("CARGO_MANIFEST_DIR"))
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| PathBuf::from("..")),
+            answers: None,
+            jobs: 1,
+            count_allocations: false,
+            parts: vec![],
+            endpoint: String::from(submit::DEFAULT_ENDPOINT),
+            mode: Mode::Interactive,
+        };
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            let mut value = || {
+                args.next()
+                    .map(|v| String::from(v.as_ref()))
+                    .ok_or_else(|| OptionsError::MissingValue(String::from(arg)))
+            };
+
+            match arg {
+                "--root" => options.root = PathBuf::from(value()?),
+                "--answers" => options.answers = Some(PathBuf::from(value()?)),
+                "--jobs" => {
+                    let jobs = value()?;
+                    options.jobs = match jobs.parse() {
+                        Ok(jobs) if jobs > 0 => jobs,
+                        _ => return Err(OptionsError::InvalidValue(String::from(arg), jobs)),
+                    };
+                }
+                "--allocations" => options.count_allocations = true,
+                "--check" => options.mode = Mode::Check,
+                "--record" => options.mode = Mode::Record,
+                "--watch" | "--submit" => {
+                    let day = value()?;
+                    let number = match day.parse() {
+                        Ok(number) => number,
+                        _ => return Err(OptionsError::InvalidValue(String::from(arg), day)),
+                    };
+                    options.mode = match arg {
+                        "--watch" => Mode::Watch(number),
+                        _ => Mode::Submit(number),
+                    };
+                }
+                "--endpoint" => options.endpoint = value()?,
+                "--part" => {
+                    let part = value()?;
+                    match part.parse() {
+                        Ok(part @ 1..=2) => options.parts.push(part),
+                        _ => return Err(OptionsError::InvalidPart(part)),
+                    }
+                }
+                unknown => return Err(OptionsError::Unknown(String::from(unknown))),
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn answers_path(&self) -> PathBuf {
+        self.answers
+            .clone()
+            .unwrap_or_else(|| self.root.join("answers.json"))
+    }
+
+    /// Rejected answers are kept next to the recorded ones
+    fn rejected_path(&self) -> PathBuf {
+        self.answers_path().with_file_name("rejected.json")
+    }
+}
+
+fn max_jobs() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// Run every day without the interface, printing a line for each part.
+/// Returns whether every day ran and matched its recorded answers
+fn run_headless(app: &mut App, events: Receiver<Event>, record: bool) -> bool {
+    let mut remaining = app.days.len();
+    app.key(ratatui::crossterm::event::KeyCode::Char('a'));
+
+    while remaining > 0 {
+        match events.recv() {
+            Ok(event @ Event::Finished(..)) => {
+                remaining -= 1;
+                app.handle(event);
+            }
+            Ok(event) => app.handle(event),
+            Err(_) => break,
+        }
+    }
+
+    for (day, status) in &app.status {
+        match status {
+            Status::Done(run) => {
+                for part in &run.report.parts {
+                    let answer = part.answer.as_deref().unwrap_or("");
+                    let check = match app.check(*day, part.part, answer) {
+                        Check::Match => String::from("ok"),
+                        Check::Regression(expected) => {
+                            format!("REGRESSION expected {:?}", expected)
+                        }
+                        Check::Unrecorded => String::from("new"),
+                    };
+
+                    let allocations = match &part.allocations {
+                        Some(a) => format!(
+                            ", peak heap {}, {} allocations",
+                            ui::format_bytes(a.peak_bytes),
+                            a.allocations
+                        ),
+                        None => String::new(),
+                    };
+
+                    println!(
+                        "day {:02} part {}: {} in {}{} ({})",
+                        day,
+                        part.part,
+                        ui::first_line(answer),
+                        ui::format_duration(part.duration_us),
+                        allocations,
+                        check
+                    );
+                }
+            }
+            Status::Failed(err) => println!("day {:02} FAILED: {}", day, err),
+            _ => {}
+        }
+    }
+
+    if record {
+        app.key(ratatui::crossterm::event::KeyCode::Char('s'));
+        if let Some(message) = &app.message {
+            println!("{}", message);
+        }
+    }
+
+    let (regressions, failures) = app.problems();
+    println!("regressions: {}, failures: {}", regressions, failures);
+
+    record || regressions + failures == 0
+}
+
+fn run_interactive(app: &mut App, events: Receiver<Event>) -> Result<(), Box<dyn Error>> {
+    let mut terminal = ratatui::init();
+    let mut last_frame = Instant::now();
+
+    let result = loop {
+        if let Err(err) = terminal.draw(|frame| ui::draw(frame, app)) {
+            break Err(err);
+        }
+
+        match event::poll(FRAME_INTERVAL) {
+            Ok(true) => match event::read() {
+                Ok(TermEvent::Key(key)) if key.kind == KeyEventKind::Press => {
+                    app.message = None;
+                    app.key(key.code);
+                }
+                Ok(_) => {}
+                Err(err) => break Err(err),
+            },
+            Ok(false) => {}
+            Err(err) => break Err(err),
+        }
+
+        while let Ok(event) = events.try_recv() {
+            app.handle(event);
+        }
+
+        if last_frame.elapsed() >= FRAME_INTERVAL {
+            app.tick();
+            last_frame = Instant::now();
+        }
+
+        if app.quit {
+            break Ok(());
+        }
+    };
+
+    ratatui::restore();
+    Ok(result?)
+}
+
+fn run_watch(options: &Options, days: &[Day], number: u32) -> Result<(), Box<dyn Error>> {
+    let day = days
+        .iter()
+        .find(|d| d.number == number)
+        .ok_or_else(|| OptionsError::InvalidValue(String::from("--watch"), number.to_string()))?;
+
+    let run_options = RunOptions {
+        count_allocations: options.count_allocations,
+        parts: options.parts.clone(),
+        ..RunOptions::default()
+    };
+
+    watch::watch(day, &options.root.join("common"), &run_options)
+}
+
+/// Solve one part of a day and submit its answer, unless that answer
+/// has already been rejected
+fn run_submit(options: &Options, days: &[Day], number: u32) -> Result<(), Box<dyn Error>> {
+    let day = days
+        .iter()
+        .find(|d| d.number == number)
+        .ok_or_else(|| OptionsError::InvalidValue(String::from("--submit"), number.to_string()))?;
+
+    let part = match options.parts.as_slice() {
+        [part] => *part,
+        _ => return Err("Submitting needs exactly one --part".into()),
+    };
+    let session = std::env::var("AOC_SESSION")
+        .map_err(|_| "Submitting needs the session cookie in AOC_SESSION")?;
+
+    let run_options = RunOptions {
+        parts: vec![part],
+        ..RunOptions::default()
+    };
+    let (sender, _events) = mpsc::channel();
+    let run = runner::run(day, &run_options, &sender)?;
+
+    let answer = run
+        .report
+        .parts
+        .iter()
+        .find(|p| p.part == part)
+        .and_then(|p| p.answer.clone())
+        .ok_or_else(|| format!("Day {} part {} gave no answer", number, part))?;
+
+    let rejected_path = options.rejected_path();
+    let mut rejected = Rejected::load(&rejected_path)?;
+    let submitter = Submitter::new(&options.endpoint, &session);
+
+    println!("day {:02} part {}: submitting {}", number, part, answer);
+    match submitter.submit(&mut rejected, number, part, &answer) {
+        Ok(verdict) => println!("{}", verdict),
+        Err(err @ SubmitError::AlreadyRejected(_)) => println!("{}", err),
+        Err(err) => return Err(err.into()),
+    }
+
+    rejected.save(&rejected_path)?;
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = Options::parse(std::env::args().skip(1))?;
+
+    let days = day::discover(&options.root)?;
+
+    let answers_path = options.answers_path();
+    let answers = Answers::load(&answers_path)?;
+
+    let (sender, events) = mpsc::channel();
+    let mut app = App::new(days, answers, answers_path, max_jobs(), sender);
+    app.jobs = options.jobs;
+    app.count_allocations = options.count_allocations;
+
+    match options.mode {
+        Mode::Interactive => run_interactive(&mut app, events)?,
+        Mode::Check | Mode::Record => {
+            if !run_headless(&mut app, events, options.mode == Mode::Record) {
+                std::process::exit(1);
+            }
+        }
+        Mode::Watch(number) => run_watch(&options, &app.days, number)?,
+        Mode::Submit(number) => run_submit(&options, &app.days, number)?,
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_options {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_defaults() {
+        let options = Options::parse(Vec::<&str>::new()).unwrap();
+
+        assert_eq!(options.jobs, 1);
+        assert!(!options.count_allocations);
+        assert_eq!(options.mode, Mode::Interactive);
+        assert_eq!(options.answers_path(), options.root.join("answers.json"));
+    }
+
+    #[test]
+    fn test_parse() {
+        let options = Options::parse(vec![
+            "--jobs",
+            "4",
+            "--check",
+            "--root",
+            "/tmp",
+            "--allocations",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            options,
+            Options {
+                root: PathBuf::from("/tmp"),
+                answers: None,
+                jobs: 4,
+                count_allocations: true,
+                parts: vec![],
+                endpoint: String::from(submit::DEFAULT_ENDPOINT),
+                mode: Mode::Check,
+            }
+        );
+    }
+
+    #[test]
+    fn test_watch() {
+        let options = Options::parse(vec!["--watch", "9", "--part", "2"]).unwrap();
+
+        assert_eq!(options.mode, Mode::Watch(9));
+        assert_eq!(options.parts, vec![2]);
+    }
+
+    #[test]
+    fn test_submit() {
+        let options = Options::parse(vec![
+            "--submit",
+            "3",
+            "--part",
+            "1",
+            "--endpoint",
+            "http://localhost:8080",
+        ])
+        .unwrap();
+
+        assert_eq!(options.mode, Mode::Submit(3));
+        assert_eq!(options.parts, vec![1]);
+        assert_eq!(options.endpoint, "http://localhost:8080");
+        assert_eq!(options.rejected_path(), options.root.join("rejected.json"));
+    }
+
+    fn invalid(arg: &str, value: &str) -> OptionsError {
+        OptionsError::InvalidValue(String::from(arg), String::from(value))
+    }
+
+    #[rstest(args, expected,
+        case(vec!["--jobs"], OptionsError::MissingValue(String::from("--jobs"))),
+        case(vec!["--jobs", "0"], invalid("--jobs", "0")),
+        case(vec!["--jobs", "many"], invalid("--jobs", "many")),
+        case(vec!["--bogus"], OptionsError::Unknown(String::from("--bogus"))),
+        case(vec!["--watch", "nine"], invalid("--watch", "nine")),
+        case(vec!["--part", "3"], OptionsError::InvalidPart(String::from("3"))),
+        case(vec!["--submit", "x"], invalid("--submit", "x")),
+        case(vec!["--endpoint"], OptionsError::MissingValue(String::from("--endpoint"))),
+    )]
+    fn test_errors(args: Vec<&str>, expected: OptionsError) {
+        assert_eq!(Options::parse(args), Err(expected));
+    }
+}
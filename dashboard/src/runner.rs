@@ -0,0 +1,215 @@
+// Runs the solution for each day in its own process, through cargo so that
+// it gets rebuilt whenever it has changed. Each run writes a report with its
+// answers and timings, which is sent back to the dashboard along with
+// progress updates over a channel.
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use common::logging;
+use common::report::Report;
+
+use crate::day::Day;
+use crate::visualisation::{self, Frame};
+
+#[derive(Debug)]
+pub struct Run {
+    pub report: Report,
+}
+
+#[derive(Debug)]
+pub enum RunError {
+    Io(io::Error),
+    /// The process failed, with the tail end of what it wrote to stderr
+    Failed(String),
+    Report(serde_json::Error),
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            RunError::Io(err) => write!(f, "{}", err),
+            RunError::Failed(stderr) => write!(f, "{}", stderr),
+            RunError::Report(err) => write!(f, "Invalid report: {}", err),
+        }
+    }
+}
+
+impl Error for RunError {}
+
+impl From<io::Error> for RunError {
+    fn from(err: io::Error) -> Self {
+        RunError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for RunError {
+    fn from(err: serde_json::Error) -> Self {
+        RunError::Report(err)
+    }
+}
+
+#[derive(Debug)]
+pub enum Event {
+    Started(u32),
+    Finished(u32, Result<Run, RunError>),
+    Frame(u32, Frame),
+    /// A run started with `visualise` has finished. Its timings are not
+    /// representative because of the logging, so only errors are kept
+    Visualised(u32, Result<(), RunError>),
+}
+
+/// Last few lines of the output of a failed process, which is
+/// usually where the reason it failed can be found
+fn tail(output: &str, count: usize) -> String {
+    let lines: Vec<&str> = output.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines[lines.len().saturating_sub(count)..].join("\n")
+}
+
+/// How a day should be run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunOptions {
+    /// Send every rendering the day logs as a frame while it runs
+    pub visualise: bool,
+    /// Build the day with the counting allocator so that its report
+    /// includes the allocations made by each part
+    pub count_allocations: bool,
+    /// Only solve these parts. Every part is solved when empty
+    pub parts: Vec<u32>,
+}
+
+/// Run the solution for a day and wait for it to finish
+pub fn run(day: &Day, options: &RunOptions, events: &Sender<Event>) -> Result<Run, RunError> {
+    let report_path = std::env::temp_dir().join(format!(
+        "dashboard-{}-day{:02}.json",
+        std::process::id(),
+        day.number
+    ));
+
+    let stdin = match day.input() {
+        Some(path) => Stdio::from(File::open(path)?),
+        None => Stdio::null(),
+    };
+
+    let mut command = Command::new("cargo");
+    command.args(["run", "--release", "--quiet"]);
+    if options.count_allocations {
+        command.args(["--features", "common/count-allocations"]);
+    }
+
+    command.args(["--", "--report"]).arg(&report_path);
+    for part in &options.parts {
+        command.args(["--part", &part.to_string()]);
+    }
+
+    command
+        .current_dir(&day.path)
+        .stdin(stdin)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+
+    if options.visualise {
+        command
+            .env(logging::ENV_VAR, "trace")
+            .env(logging::FORMAT_ENV_VAR, "json");
+    } else {
+        command.env_remove(logging::ENV_VAR);
+    }
+
+    let mut child = command.spawn()?;
+    let mut stderr = String::new();
+
+    if let Some(output) = child.stderr.take() {
+        if options.visualise {
+            for line in BufReader::new(output).lines() {
+                let line = line?;
+                match visualisation::parse_frame(&line) {
+                    Some(frame) => {
+                        let _ = events.send(Event::Frame(day.number, frame));
+                    }
+                    None => {
+                        stderr.push_str(&line);
+                        stderr.push('\n');
+                    }
+                }
+            }
+        } else {
+            BufReader::new(output).read_to_string(&mut stderr)?;
+        }
+    }
+
+    let status = child.wait()?;
+
+    if !status.success() {
+        return Err(RunError::Failed(tail(&stderr, 3)));
+    }
+
+    let contents = fs::read_to_string(&report_path)?;
+    fs::remove_file(&report_path)?;
+
+    Ok(Run {
+        report: serde_json::from_str(&contents)?,
+    })
+}
+
+/// Take the next day off the queue. The queue is only locked for as
+/// long as it takes to do so, never while the day is running
+fn next_day(queue: &Mutex<VecDeque<Day>>) -> Option<Day> {
+    queue.lock().ok()?.pop_front()
+}
+
+/// Run every day given on a pool of `jobs` threads, sending an event as
+/// each one starts and finishes. With a single job the days run in order
+pub fn run_all(days: Vec<Day>, jobs: usize, options: RunOptions, events: Sender<Event>) {
+    let queue = Arc::new(Mutex::new(days.into_iter().collect::<VecDeque<Day>>()));
+
+    for _ in 0..jobs.max(1) {
+        let queue = Arc::clone(&queue);
+        let events = events.clone();
+        let options = options.clone();
+
+        thread::spawn(move || {
+            while let Some(day) = next_day(&queue) {
+                let _ = events.send(Event::Started(day.number));
+                let result = run(&day, &options, &events);
+                let _ = events.send(Event::Finished(day.number, result));
+            }
+        });
+    }
+}
+
+/// Run a single day in the background while collecting its renderings
+pub fn visualise(day: Day, events: Sender<Event>) {
+    thread::spawn(move || {
+        let options = RunOptions {
+            visualise: true,
+            ..RunOptions::default()
+        };
+        let result = run(&day, &options, &events).map(|_| ());
+        let _ = events.send(Event::Visualised(day.number, result));
+    });
+}
+
+#[cfg(test)]
+mod test_runner {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(
+        output,
+        expected,
+        case("", ""),
+        case("one\n\ntwo\n", "one\ntwo"),
+        case("a\nb\nc\nd\n\n", "b\nc\nd")
+    )]
+    fn test_tail(output: &str, expected: &str) {
+        assert_eq!(tail(output, 3), expected);
+    }
+}
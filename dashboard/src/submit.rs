@@ -0,0 +1,486 @@
+// Submits answers to the puzzle website and works out from the page it
+// sends back whether the answer was right. Answers which were rejected are
+// remembered, along with whether they were too high or too low, so that
+// the same answer, or one which is known to be out of range, is never
+// sent twice. Stored as JSON like answers.json, mapping each day and part
+// to the rejected answers and why each was rejected.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::answers::AnswersError;
+
+pub const DEFAULT_ENDPOINT: &str = "https://adventofcode.com";
+const YEAR: u32 = 2018;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// Another answer was sent too recently, with how long is left to wait
+    RateLimited(Option<Duration>),
+    /// The part has already been solved or is not unlocked yet
+    WrongLevel,
+}
+
+impl Verdict {
+    fn is_rejection(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::RateLimited(_) => "rate limited",
+            Verdict::WrongLevel => "wrong level",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        match name {
+            "incorrect" => Some(Verdict::Incorrect),
+            "too high" => Some(Verdict::TooHigh),
+            "too low" => Some(Verdict::TooLow),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Verdict::RateLimited(Some(wait)) => {
+                write!(f, "rate limited, {}s left to wait", wait.as_secs())
+            }
+            Verdict::WrongLevel => write!(f, "already solved or not unlocked yet"),
+            verdict => write!(f, "{}", verdict.name()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Http(Box<ureq::Error>),
+    /// The website answered with something other than 200 OK
+    Status(u16),
+    /// The page sent back did not say what happened to the answer
+    Unrecognised(String),
+    /// The answer was not sent because it is known to be wrong
+    AlreadyRejected(Verdict),
+    Answers(AnswersError),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            SubmitError::Http(err) => write!(f, "{}", err),
+            SubmitError::Status(status) => write!(f, "Unexpected status: {}", status),
+            SubmitError::Unrecognised(page) => write!(f, "Unrecognised response: {}", page),
+            SubmitError::AlreadyRejected(verdict) => {
+                write!(f, "Not submitted, already known to be {}", verdict)
+            }
+            SubmitError::Answers(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for SubmitError {}
+
+impl From<ureq::Error> for SubmitError {
+    fn from(err: ureq::Error) -> Self {
+        SubmitError::Http(Box::new(err))
+    }
+}
+
+impl From<AnswersError> for SubmitError {
+    fn from(err: AnswersError) -> Self {
+        SubmitError::Answers(err)
+    }
+}
+
+/// Parses a wait such as "You have 4m 26s left to wait"
+fn parse_wait(page: &str) -> Option<Duration> {
+    let start = page.find("You have ")? + "You have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+
+    let mut seconds: u64 = 0;
+    for token in page[start..end].split_whitespace() {
+        let (index, unit) = token.char_indices().last()?;
+        let value: u64 = token[..index].parse().ok()?;
+        let scale = match unit {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        seconds = value
+            .checked_mul(scale)
+            .and_then(|value| seconds.checked_add(value))?;
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// Works out the verdict from the page sent back after submitting
+pub fn parse_response(page: &str) -> Option<Verdict> {
+    if page.contains("That's the right answer") {
+        Some(Verdict::Correct)
+    } else if page.contains("your answer is too high") {
+        Some(Verdict::TooHigh)
+    } else if page.contains("your answer is too low") {
+        Some(Verdict::TooLow)
+    } else if page.contains("That's not the right answer") {
+        Some(Verdict::Incorrect)
+    } else if page.contains("You gave an answer too recently") {
+        Some(Verdict::RateLimited(parse_wait(page)))
+    } else if page.contains("You don't seem to be solving the right level") {
+        Some(Verdict::WrongLevel)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Rejected {
+    days: BTreeMap<u32, BTreeMap<u32, BTreeMap<String, String>>>,
+}
+
+impl Rejected {
+    /// Load rejected answers. A missing file means none have been rejected yet
+    pub fn load(path: &Path) -> Result<Rejected, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Rejected {
+                days: serde_json::from_str(&contents)?,
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Rejected::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        let mut contents = serde_json::to_string_pretty(&self.days)?;
+        contents.push('\n');
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Remembers the answer if the verdict rejected it
+    pub fn record(&mut self, day: u32, part: u32, answer: &str, verdict: Verdict) {
+        if verdict.is_rejection() {
+            self.days
+                .entry(day)
+                .or_default()
+                .entry(part)
+                .or_default()
+                .insert(String::from(answer), String::from(verdict.name()));
+        }
+    }
+
+    /// Why the answer would be rejected, going by the answers rejected
+    /// before. Numbers are compared against any which were too high or low
+    pub fn check(&self, day: u32, part: u32, answer: &str) -> Option<Verdict> {
+        let rejected = self.days.get(&day)?.get(&part)?;
+
+        if let Some(verdict) = rejected.get(answer) {
+            return Verdict::from_name(verdict);
+        }
+
+        let answer: i64 = answer.trim().parse().ok()?;
+        rejected.iter().find_map(|(previous, verdict)| {
+            let previous: i64 = previous.parse().ok()?;
+            match Verdict::from_name(verdict)? {
+                Verdict::TooHigh if answer >= previous => Some(Verdict::TooHigh),
+                Verdict::TooLow if answer <= previous => Some(Verdict::TooLow),
+                _ => None,
+            }
+        })
+    }
+}
+
+pub struct Submitter {
+    endpoint: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Submitter {
+    pub fn new(endpoint: &str, session: &str) -> Submitter {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+
+        Submitter {
+            endpoint: String::from(endpoint.trim_end_matches('/')),
+            session: String::from(session.trim()),
+            agent,
+        }
+    }
+
+    /// Sends the answer without checking whether it was rejected before
+    pub fn post(&self, day: u32, part: u32, answer: &str) -> Result<Verdict, SubmitError> {
+        let url = format!("{}/{}/day/{}/answer", self.endpoint, YEAR, day);
+        let level = part.to_string();
+
+        let mut response = self
+            .agent
+            .post(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .send_form([("level", level.as_str()), ("answer", answer)])?;
+
+        let status = response.status().as_u16();
+        if status != 200 {
+            return Err(SubmitError::Status(status));
+        }
+
+        let page = response.body_mut().read_to_string()?;
+        parse_response(&page)
+            .ok_or_else(|| SubmitError::Unrecognised(page.chars().take(200).collect::<String>()))
+    }
+
+    /// Sends the answer unless it is already known to be wrong,
+    /// remembering it if it is rejected
+    pub fn submit(
+        &self,
+        rejected: &mut Rejected,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<Verdict, SubmitError> {
+        if let Some(verdict) = rejected.check(day, part, answer) {
+            return Err(SubmitError::AlreadyRejected(verdict));
+        }
+
+        let verdict = self.post(day, part, answer)?;
+        rejected.record(day, part, answer, verdict);
+        Ok(verdict)
+    }
+}
+
+#[cfg(test)]
+mod test_verdicts {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(page, expected,
+        case("<p>That's the right answer!  You are one gold star closer.</p>", Some(Verdict::Correct)),
+        case("<p>That's not the right answer.  If you're stuck...</p>", Some(Verdict::Incorrect)),
+        case("<p>That's not the right answer; your answer is too high.</p>", Some(Verdict::TooHigh)),
+        case("<p>That's not the right answer; your answer is too low.</p>", Some(Verdict::TooLow)),
+        case(
+            "<p>You gave an answer too recently; you have to wait. You have 4m 26s left to wait.</p>",
+            Some(Verdict::RateLimited(Some(Duration::from_secs(266))))
+        ),
+        case(
+            "<p>You gave an answer too recently. You have 36s left to wait.</p>",
+            Some(Verdict::RateLimited(Some(Duration::from_secs(36))))
+        ),
+        case("<p>You gave an answer too recently.</p>", Some(Verdict::RateLimited(None))),
+        case(
+            "<p>You gave an answer too recently. You have 5é left to wait.</p>",
+            Some(Verdict::RateLimited(None))
+        ),
+        case(
+            "<p>You gave an answer too recently. You have 18446744073709551615h left to wait.</p>",
+            Some(Verdict::RateLimited(None))
+        ),
+        case(
+            "<p>You gave an answer too recently. You have 18446744073709551615s 1s left to wait.</p>",
+            Some(Verdict::RateLimited(None))
+        ),
+        case("<p>You don't seem to be solving the right level.</p>", Some(Verdict::WrongLevel)),
+        case("<html>Puzzle inputs differ by user.</html>", None),
+    )]
+    fn test_parse_response(page: &str, expected: Option<Verdict>) {
+        assert_eq!(parse_response(page), expected);
+    }
+
+    #[test]
+    fn test_rejected() {
+        let mut rejected = Rejected::default();
+        rejected.record(1, 2, "100", Verdict::TooHigh);
+        rejected.record(1, 2, "10", Verdict::TooLow);
+        rejected.record(1, 2, "50", Verdict::Incorrect);
+        rejected.record(1, 2, "42", Verdict::Correct);
+        rejected.record(1, 2, "43", Verdict::RateLimited(None));
+
+        assert_eq!(rejected.check(1, 2, "50"), Some(Verdict::Incorrect));
+        assert_eq!(rejected.check(1, 2, "150"), Some(Verdict::TooHigh));
+        assert_eq!(rejected.check(1, 2, "10"), Some(Verdict::TooLow));
+        assert_eq!(rejected.check(1, 2, "-3"), Some(Verdict::TooLow));
+        assert_eq!(rejected.check(1, 2, "42"), None);
+        assert_eq!(rejected.check(1, 2, "43"), None);
+        assert_eq!(rejected.check(1, 1, "150"), None);
+        assert_eq!(rejected.check(1, 2, "abc"), None);
+    }
+
+    #[test]
+    fn test_save_and_load() -> Result<(), AnswersError> {
+        let path = std::env::temp_dir().join(format!("rejected-{}.json", std::process::id()));
+
+        assert_eq!(Rejected::load(&path)?, Rejected::default());
+
+        let mut rejected = Rejected::default();
+        rejected.record(9, 1, "8316", Verdict::TooLow);
+        rejected.save(&path)?;
+
+        let result = Rejected::load(&path);
+        fs::remove_file(&path)?;
+
+        assert_eq!(result?, rejected);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_submitter {
+    use super::*;
+    use std::sync::mpsc;
+    use std::thread;
+
+    #[derive(Debug, PartialEq)]
+    struct Received {
+        url: String,
+        cookie: Option<String>,
+        body: String,
+    }
+
+    /// Stands in for the website, answering each request with the next
+    /// of the given pages and passing on what was requested
+    fn fake_server(pages: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<Received>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", server.server_addr());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, page) in pages {
+                let mut request = match server.recv() {
+                    Ok(request) => request,
+                    Err(_) => return,
+                };
+
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|h| h.field.equiv("Cookie"))
+                    .map(|h| h.value.to_string());
+
+                sender
+                    .send(Received {
+                        url: request.url().to_string(),
+                        cookie,
+                        body,
+                    })
+                    .unwrap();
+
+                let response = tiny_http::Response::from_string(page).with_status_code(status);
+                request.respond(response).unwrap();
+            }
+        });
+
+        (endpoint, receiver)
+    }
+
+    #[test]
+    fn test_correct() {
+        let (endpoint, requests) = fake_server(vec![(200, "<p>That's the right answer!</p>")]);
+        let submitter = Submitter::new(&endpoint, "secret\n");
+        let mut rejected = Rejected::default();
+
+        let verdict = submitter.submit(&mut rejected, 9, 2, "3156297594");
+
+        assert_eq!(verdict.unwrap(), Verdict::Correct);
+        assert_eq!(
+            requests.recv().unwrap(),
+            Received {
+                url: String::from("/2018/day/9/answer"),
+                cookie: Some(String::from("session=secret")),
+                body: String::from("level=2&answer=3156297594"),
+            }
+        );
+        assert_eq!(rejected, Rejected::default());
+    }
+
+    #[test]
+    fn test_not_resubmitted() {
+        let (endpoint, requests) = fake_server(vec![
+            (
+                200,
+                "<p>That's not the right answer; your answer is too high.</p>",
+            ),
+            (
+                200,
+                "<p>That's not the right answer; your answer is too low.</p>",
+            ),
+        ]);
+        let submitter = Submitter::new(&endpoint, "secret");
+        let mut rejected = Rejected::default();
+
+        let verdict = submitter.submit(&mut rejected, 1, 1, "600");
+        assert_eq!(verdict.unwrap(), Verdict::TooHigh);
+
+        // known to be too high without asking again
+        for answer in &["600", "700"] {
+            match submitter.submit(&mut rejected, 1, 1, answer) {
+                Err(SubmitError::AlreadyRejected(Verdict::TooHigh)) => {}
+                other => panic!("expected a local rejection, got {:?}", other),
+            }
+        }
+
+        let verdict = submitter.submit(&mut rejected, 1, 1, "500");
+        assert_eq!(verdict.unwrap(), Verdict::TooLow);
+        assert_eq!(requests.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_rate_limited() {
+        let (endpoint, _requests) = fake_server(vec![(
+            200,
+            "<p>You gave an answer too recently. You have 1m 5s left to wait.</p>",
+        )]);
+        let submitter = Submitter::new(&endpoint, "secret");
+        let mut rejected = Rejected::default();
+
+        let verdict = submitter.submit(&mut rejected, 1, 1, "525");
+
+        assert_eq!(
+            verdict.unwrap(),
+            Verdict::RateLimited(Some(Duration::from_secs(65)))
+        );
+        // the answer may still be right, so it can be sent again later
+        assert_eq!(rejected.check(1, 1, "525"), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let (endpoint, _requests) = fake_server(vec![
+            (500, "Internal Server Error"),
+            (200, "<html>Puzzle inputs differ by user.</html>"),
+        ]);
+        let submitter = Submitter::new(&endpoint, "secret");
+
+        assert!(matches!(
+            submitter.post(1, 1, "525"),
+            Err(SubmitError::Status(500))
+        ));
+        assert!(matches!(
+            submitter.post(1, 1, "525"),
+            Err(SubmitError::Unrecognised(_))
+        ));
+    }
+}
@@ -0,0 +1,269 @@
+// Drawing the dashboard. One row is shown for every part of every day,
+// with the selected day highlighted and the visualisation, when open,
+// taking over the right hand side of the screen.
+
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table};
+use ratatui::Frame;
+
+use crate::answers::Check;
+use crate::app::{App, Status};
+use crate::visualisation::{Visualisation, MAX_FRAMES};
+
+pub fn format_duration(micros: u64) -> String {
+    if micros < 1_000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.1}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.2}s", micros as f64 / 1_000_000.0)
+    }
+}
+
+pub fn format_memory(kb: u64) -> String {
+    if kb < 1024 {
+        format!("{} KiB", kb)
+    } else {
+        format!("{:.1} MiB", kb as f64 / 1024.0)
+    }
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else {
+        format_memory(bytes as u64 / 1024)
+    }
+}
+
+/// Answers such as the message from day 10 span several lines,
+/// only the first of which fits in the table
+pub fn first_line(answer: &str) -> String {
+    let mut lines = answer.lines();
+    let first = lines.next().unwrap_or("");
+
+    if lines.next().is_some() {
+        format!("{}…", first)
+    } else {
+        String::from(first)
+    }
+}
+
+fn check_cell(check: Check) -> Cell<'static> {
+    match check {
+        Check::Match => Cell::from("ok").style(Style::default().fg(Color::Green)),
+        Check::Regression(expected) => Cell::from(format!("expected {}", first_line(&expected)))
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+        Check::Unrecorded => Cell::from("new").style(Style::default().fg(Color::Yellow)),
+    }
+}
+
+fn day_rows(app: &App, number: u32, status: &Status) -> Vec<Row<'static>> {
+    let name = format!("{:02}", number);
+
+    match status {
+        Status::Pending => vec![Row::new(vec![name, String::new(), String::from("pending")])],
+        Status::Running => vec![Row::new(vec![name, String::new(), String::from("running")])],
+        Status::Failed(err) => vec![Row::new(vec![
+            Cell::from(name),
+            Cell::from(""),
+            Cell::from("failed").style(Style::default().fg(Color::Red)),
+            Cell::from(first_line(err)),
+        ])],
+        Status::Done(run) if run.report.parts.is_empty() => {
+            vec![Row::new(vec![name, String::new(), String::from("done")])]
+        }
+        Status::Done(run) => run
+            .report
+            .parts
+            .iter()
+            .enumerate()
+            .map(|(index, part)| {
+                let answer = part.answer.as_deref().unwrap_or("");
+                let (heap, allocations) = match &part.allocations {
+                    Some(a) => (format_bytes(a.peak_bytes), a.allocations.to_string()),
+                    None => (String::new(), String::new()),
+                };
+                // the name and memory are for the whole day, so only shown once
+                let (name, memory) = match (index, run.report.peak_rss_kb) {
+                    (0, Some(kb)) => (name.clone(), format_memory(kb)),
+                    (0, None) => (name.clone(), String::new()),
+                    _ => (String::new(), String::new()),
+                };
+
+                Row::new(vec![
+                    Cell::from(name),
+                    Cell::from(part.part.to_string()),
+                    Cell::from("done"),
+                    Cell::from(first_line(answer)),
+                    Cell::from(format_duration(part.duration_us)),
+                    Cell::from(memory),
+                    Cell::from(heap),
+                    Cell::from(allocations),
+                    check_cell(app.check(number, part.part, answer)),
+                ])
+            })
+            .collect(),
+    }
+}
+
+fn rows(app: &App) -> Vec<Row<'static>> {
+    let mut rows = vec![];
+
+    for (index, day) in app.days.iter().enumerate() {
+        let day_rows = day_rows(app, day.number, &app.status[&day.number]);
+
+        if index == app.selected {
+            let style = Style::default().add_modifier(Modifier::REVERSED);
+            rows.extend(day_rows.into_iter().map(|row| row.style(style)));
+        } else {
+            rows.extend(day_rows);
+        }
+    }
+
+    rows
+}
+
+fn draw_table(frame: &mut Frame, app: &App, area: Rect) {
+    let header = Row::new(vec![
+        "Day", "Part", "Status", "Answer", "Time", "Memory", "Heap", "Allocs", "Check",
+    ])
+    .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let widths = [
+        Constraint::Length(4),
+        Constraint::Length(5),
+        Constraint::Length(8),
+        Constraint::Min(16),
+        Constraint::Length(9),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(8),
+        Constraint::Length(16),
+    ];
+
+    let mut mode = if app.jobs == 1 {
+        String::from("sequential")
+    } else {
+        format!("parallel x{}", app.jobs)
+    };
+    if app.count_allocations {
+        mode.push_str(", counting allocations");
+    }
+
+    let table = Table::new(rows(app), widths).header(header).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Advent of Code 2018 ({}) ", mode)),
+    );
+
+    frame.render_widget(table, area);
+}
+
+fn draw_visualisation(frame: &mut Frame, visualisation: &Visualisation, area: Rect) {
+    let title = match visualisation.current() {
+        Some(current) => format!(
+            " Day {} - {} ({}/{}{}) ",
+            visualisation.day,
+            current.title,
+            visualisation.index + 1,
+            visualisation.frames.len(),
+            if visualisation.dropped > 0 {
+                format!(", first {} only", MAX_FRAMES)
+            } else {
+                String::new()
+            }
+        ),
+        None => format!(" Day {} - waiting for frames ", visualisation.day),
+    };
+
+    let map = visualisation
+        .current()
+        .map(|f| f.map.as_str())
+        .unwrap_or("Days without a visualisation do not log any frames");
+
+    let paragraph = Paragraph::new(map).block(Block::default().borders(Borders::ALL).title(title));
+
+    frame.render_widget(paragraph, area);
+}
+
+fn help(app: &App) -> Line<'static> {
+    if let Some(message) = &app.message {
+        return Line::from(message.clone());
+    }
+
+    let (regressions, failures) = app.problems();
+    let keys = if app.visualisation.is_some() {
+        "space pause  ←/→ step  esc close  q quit"
+    } else {
+        "a run all  r run day  v visualise  p parallel  m count allocations  s record answers  q quit"
+    };
+
+    let line = format!(
+        "{}   regressions: {}  failures: {}",
+        keys, regressions, failures
+    );
+    if regressions + failures > 0 {
+        Line::styled(line, Style::default().fg(Color::Red))
+    } else {
+        Line::from(line)
+    }
+}
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [main, footer] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+
+    match &app.visualisation {
+        Some(visualisation) => {
+            let [table, pane] =
+                Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)])
+                    .areas(main);
+            draw_table(frame, app, table);
+            draw_visualisation(frame, visualisation, pane);
+        }
+        None => draw_table(frame, app, main),
+    }
+
+    frame.render_widget(Paragraph::new(help(app)), footer);
+}
+
+#[cfg(test)]
+mod test_format {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(
+        micros,
+        expected,
+        case(23, "23µs"),
+        case(12_756, "12.8ms"),
+        case(7_042_529, "7.04s")
+    )]
+    fn test_format_duration(micros: u64, expected: &str) {
+        assert_eq!(format_duration(micros), expected);
+    }
+
+    #[rstest(kb, expected, case(512, "512 KiB"), case(164_968, "161.1 MiB"))]
+    fn test_format_memory(kb: u64, expected: &str) {
+        assert_eq!(format_memory(kb), expected);
+    }
+
+    #[rstest(
+        bytes,
+        expected,
+        case(96, "96 B"),
+        case(4096, "4 KiB"),
+        case(10_223_648, "9.8 MiB")
+    )]
+    fn test_format_bytes(bytes: usize, expected: &str) {
+        assert_eq!(format_bytes(bytes), expected);
+    }
+
+    #[rstest(answer, expected, case("525", "525"), case("#..#\n#..#", "#..#…"))]
+    fn test_first_line(answer: &str, expected: &str) {
+        assert_eq!(first_line(answer), expected);
+    }
+}
@@ -0,0 +1,137 @@
+// Days which simulate something, such as the carts on day 13 or the battle
+// on day 15, log a rendering of the map with their trace events. When run
+// with AOC_LOG_FORMAT=json every event is a line of JSON, so the renderings
+// can be picked out of the log and played back as an animation.
+
+use serde_json::Value;
+
+/// Keep memory bounded for days which simulate thousands of steps
+pub const MAX_FRAMES: usize = 2000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub title: String,
+    pub map: String,
+}
+
+/// Pick the rendering out of a log line, if it has one
+pub fn parse_frame(line: &str) -> Option<Frame> {
+    let event: Value = serde_json::from_str(line).ok()?;
+    let fields = event.get("fields")?;
+    let map = fields.get("map")?.as_str()?;
+
+    let message = fields.get("message").and_then(Value::as_str).unwrap_or("");
+    let title = match (fields.get("part"), fields.get("step")) {
+        (Some(part), Some(step)) => format!("part {} {} {}", part, message, step),
+        _ => String::from(message),
+    };
+
+    Some(Frame {
+        title,
+        map: String::from(map),
+    })
+}
+
+#[derive(Debug)]
+pub struct Visualisation {
+    pub day: u32,
+    pub frames: Vec<Frame>,
+    pub index: usize,
+    pub playing: bool,
+    /// Frames which were thrown away after reaching MAX_FRAMES
+    pub dropped: usize,
+}
+
+impl Visualisation {
+    pub fn new(day: u32) -> Visualisation {
+        Visualisation {
+            day,
+            frames: vec![],
+            index: 0,
+            playing: true,
+            dropped: 0,
+        }
+    }
+
+    pub fn push(&mut self, frame: Frame) {
+        if self.frames.len() < MAX_FRAMES {
+            self.frames.push(frame);
+        } else {
+            self.dropped += 1;
+        }
+    }
+
+    pub fn current(&self) -> Option<&Frame> {
+        self.frames.get(self.index)
+    }
+
+    /// Move on to the next frame while playing
+    pub fn tick(&mut self) {
+        if self.playing {
+            self.forward();
+        }
+    }
+
+    pub fn forward(&mut self) {
+        if self.index + 1 < self.frames.len() {
+            self.index += 1;
+        }
+    }
+
+    pub fn back(&mut self) {
+        self.index = self.index.saturating_sub(1);
+    }
+
+    pub fn toggle(&mut self) {
+        self.playing = !self.playing;
+    }
+}
+
+#[cfg(test)]
+mod test_visualisation {
+    use super::*;
+
+    #[test]
+    fn test_parse_frame() {
+        let line = r##"{"timestamp":"2018-12-15T00:00:00Z","level":"TRACE","fields":{"message":"round","day":15,"part":1,"step":3,"map":"#E.G#"},"target":"day15::game"}"##;
+
+        let expected = Frame {
+            title: String::from("part 1 round 3"),
+            map: String::from("#E.G#"),
+        };
+
+        assert_eq!(parse_frame(line), Some(expected));
+    }
+
+    #[test]
+    fn test_parse_frame_without_map() {
+        let line = r#"{"level":"DEBUG","fields":{"message":"crash","day":13,"x":1,"y":2}}"#;
+
+        assert_eq!(parse_frame(line), None);
+        assert_eq!(parse_frame("error: could not compile"), None);
+    }
+
+    #[test]
+    fn test_playback() {
+        let mut visualisation = Visualisation::new(15);
+        assert_eq!(visualisation.current(), None);
+
+        for step in 0..MAX_FRAMES + 5 {
+            visualisation.push(Frame {
+                title: step.to_string(),
+                map: String::new(),
+            });
+        }
+        assert_eq!(visualisation.frames.len(), MAX_FRAMES);
+        assert_eq!(visualisation.dropped, 5);
+
+        visualisation.tick();
+        visualisation.tick();
+        assert_eq!(visualisation.index, 2);
+
+        visualisation.toggle();
+        visualisation.tick();
+        visualisation.back();
+        assert_eq!(visualisation.current().map(|f| f.title.as_str()), Some("1"));
+    }
+}
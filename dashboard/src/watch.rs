@@ -0,0 +1,231 @@
+// Watching a single day while working on it. The day is run again whenever
+// its input or source changes, including the code shared through common,
+// and every run is compared with the one before it so that changes to the
+// answers and timings stand out.
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvError};
+use std::time::Duration;
+
+use common::report::Report;
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::day::Day;
+use crate::runner::{self, RunOptions};
+use crate::ui;
+
+/// Editors often save a file in several steps, so changes are only acted
+/// on once nothing else has changed for this long
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// Files and directories which trigger a new run when they change
+#[derive(Debug, PartialEq)]
+pub struct Sources {
+    pub inputs: Vec<PathBuf>,
+    pub directories: Vec<PathBuf>,
+}
+
+impl Sources {
+    pub fn new(day: &Day, common: &Path) -> Sources {
+        let mut directories = vec![day.path.join("src")];
+        if common.join("src").is_dir() {
+            directories.push(common.join("src"));
+        }
+
+        Sources {
+            inputs: day.input().into_iter().collect(),
+            directories,
+        }
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.inputs.iter().any(|input| path == input)
+            || self.directories.iter().any(|dir| path.starts_with(dir))
+    }
+}
+
+fn format_change(previous: u64, current: u64) -> String {
+    if previous == 0 {
+        return String::new();
+    }
+
+    let change = (current as f64 - previous as f64) / previous as f64 * 100.0;
+    format!(", {:+.0}%", change)
+}
+
+/// Describe each part of a run, along with how it differs from the run
+/// before. Parts only found in the previous run are listed as missing
+pub fn compare(previous: Option<&Report>, current: &Report) -> Vec<String> {
+    let mut lines = vec![];
+
+    for part in &current.parts {
+        let answer = part.answer.as_deref().unwrap_or("");
+        let before = previous.and_then(|r| r.parts.iter().find(|p| p.part == part.part));
+
+        let line = match before {
+            Some(before) => {
+                let previous_answer = before.answer.as_deref().unwrap_or("");
+                let answer_change = if previous_answer == answer {
+                    String::from("unchanged")
+                } else {
+                    format!("was {}", ui::first_line(previous_answer))
+                };
+
+                format!(
+                    "part {}: {} ({}) in {} (was {}{})",
+                    part.part,
+                    ui::first_line(answer),
+                    answer_change,
+                    ui::format_duration(part.duration_us),
+                    ui::format_duration(before.duration_us),
+                    format_change(before.duration_us, part.duration_us)
+                )
+            }
+            None => format!(
+                "part {}: {} in {}",
+                part.part,
+                ui::first_line(answer),
+                ui::format_duration(part.duration_us)
+            ),
+        };
+        lines.push(line);
+    }
+
+    if let Some(previous) = previous {
+        for part in &previous.parts {
+            if !current.parts.iter().any(|p| p.part == part.part) {
+                lines.push(format!("part {}: missing", part.part));
+            }
+        }
+    }
+
+    lines
+}
+
+/// Block until something in the sources changes, then wait for it to settle
+fn wait_for_change(
+    changes: &Receiver<notify::Result<notify::Event>>,
+    sources: &Sources,
+) -> Result<(), RecvError> {
+    loop {
+        if let Ok(event) = changes.recv()? {
+            let modified = matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            );
+
+            if modified && event.paths.iter().any(|p| sources.contains(p)) {
+                break;
+            }
+        }
+    }
+
+    while changes.recv_timeout(SETTLE).is_ok() {}
+    Ok(())
+}
+
+/// Run a day every time its sources change, until interrupted
+pub fn watch(day: &Day, common: &Path, options: &RunOptions) -> Result<(), Box<dyn Error>> {
+    let sources = Sources::new(day, common);
+
+    let (sender, changes) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+
+    for directory in &sources.directories {
+        watcher.watch(directory, RecursiveMode::Recursive)?;
+    }
+    // the directory holding each input is watched rather than the input
+    // itself, as editors often save by replacing the file
+    for input in &sources.inputs {
+        if let Some(parent) = input.parent() {
+            watcher.watch(parent, RecursiveMode::NonRecursive)?;
+        }
+    }
+
+    // frames are only sent when visualising, which watching never does
+    let (events, _) = mpsc::channel();
+    let mut previous: Option<Report> = None;
+
+    loop {
+        println!("Running day {:02}", day.number);
+
+        match runner::run(day, options, &events) {
+            Ok(run) => {
+                for line in compare(previous.as_ref(), &run.report) {
+                    println!("  {}", line);
+                }
+                previous = Some(run.report);
+            }
+            Err(err) => println!("  FAILED: {}", err),
+        }
+
+        println!("Waiting for changes to day {:02}", day.number);
+        wait_for_change(&changes, &sources)?;
+    }
+}
+
+#[cfg(test)]
+mod test_watch {
+    use super::*;
+
+    fn report(answers: &[(&str, u64)]) -> Report {
+        let mut report = Report::new(9);
+        for (index, (answer, duration)) in answers.iter().enumerate() {
+            let part = index as u32 + 1;
+            report.answer(part, answer);
+            report.parts[index].duration_us = *duration;
+        }
+        report
+    }
+
+    #[test]
+    fn test_first_run() {
+        let current = report(&[("32", 900), ("22563", 12_000)]);
+
+        assert_eq!(
+            compare(None, &current),
+            vec!["part 1: 32 in 900µs", "part 2: 22563 in 12.0ms"]
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let previous = report(&[("32", 1000), ("22563", 12_000)]);
+        let current = report(&[("32", 500), ("8317", 15_000)]);
+
+        assert_eq!(
+            compare(Some(&previous), &current),
+            vec![
+                "part 1: 32 (unchanged) in 500µs (was 1.0ms, -50%)",
+                "part 2: 8317 (was 22563) in 15.0ms (was 12.0ms, +25%)",
+            ]
+        );
+
+        let current = report(&[("32", 0)]);
+        assert_eq!(
+            compare(Some(&previous), &current),
+            vec![
+                "part 1: 32 (unchanged) in 0µs (was 1.0ms, -100%)",
+                "part 2: missing"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sources() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let day = Day {
+            number: 9,
+            path: root.join("day09"),
+        };
+
+        let sources = Sources::new(&day, &root.join("common"));
+
+        assert!(sources.contains(&day.path.join("input.txt")));
+        assert!(sources.contains(&day.path.join("src").join("game.rs")));
+        assert!(sources.contains(&root.join("common").join("src").join("report.rs")));
+        assert!(!sources.contains(&day.path.join("target").join("release")));
+        assert!(!sources.contains(&day.path.join("Cargo.lock")));
+    }
+}
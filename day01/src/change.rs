@@ -0,0 +1,112 @@
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Change {
+    pub value: i64,
+    /// Line of the input the change was on, counting from 1
+    pub line: usize,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum FrequencyError {
+    Invalid { token: String, line: usize },
+    Overflow { line: usize },
+}
+
+impl std::fmt::Display for FrequencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrequencyError::Invalid { token, line } => {
+                write!(f, "Invalid change {:?} on line {}", token, line)
+            }
+            FrequencyError::Overflow { line } => {
+                write!(f, "Frequency overflows at the change on line {}", line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FrequencyError {}
+
+/// Parses changes given one per line, or separated by commas or spaces
+/// as in the examples, such as "+1, -2, +3"
+pub fn parse_changes(contents: &str) -> Result<Vec<Change>, FrequencyError> {
+    let mut changes = vec![];
+
+    for (number, text) in contents.lines().enumerate() {
+        let line = number + 1;
+
+        for token in text
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+        {
+            let value = token.parse().map_err(|_| FrequencyError::Invalid {
+                token: String::from(token),
+                line,
+            })?;
+            changes.push(Change { value, line });
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Applies the change, failing rather than wrapping around
+pub fn apply(frequency: i64, change: &Change) -> Result<i64, FrequencyError> {
+    frequency
+        .checked_add(change.value)
+        .ok_or(FrequencyError::Overflow { line: change.line })
+}
+
+#[cfg(test)]
+mod test_parse_changes {
+    use super::*;
+    use rstest::rstest;
+
+    fn values(contents: &str) -> Vec<i64> {
+        parse_changes(contents)
+            .unwrap()
+            .iter()
+            .map(|c| c.value)
+            .collect()
+    }
+
+    #[rstest(contents, expected,
+        case("+1\n-2\n+3", vec![1, -2, 3]),
+        case("+1, -2, +3", vec![1, -2, 3]),
+        case("+1 -2 +3", vec![1, -2, 3]),
+        case("+1,-2,\n+3,", vec![1, -2, 3]),
+        case("+9999999999", vec![9_999_999_999]),
+        case("", vec![]),
+    )]
+    fn test_formats(contents: &str, expected: Vec<i64>) {
+        assert_eq!(values(contents), expected);
+    }
+
+    #[test]
+    fn test_lines() {
+        let lines: Vec<usize> = parse_changes("+1, +2\n\n-3")
+            .unwrap()
+            .iter()
+            .map(|c| c.line)
+            .collect();
+        assert_eq!(lines, vec![1, 1, 3]);
+    }
+
+    #[rstest(contents, expected,
+        case("+1\n+2\nthree", FrequencyError::Invalid { token: String::from("three"), line: 3 }),
+        case("+1, 2x", FrequencyError::Invalid { token: String::from("2x"), line: 1 }),
+        case("+1\n--1", FrequencyError::Invalid { token: String::from("--1"), line: 2 }),
+    )]
+    fn test_invalid(contents: &str, expected: FrequencyError) {
+        assert_eq!(parse_changes(contents), Err(expected));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let change = Change { value: 1, line: 7 };
+        assert_eq!(apply(41, &change), Ok(42));
+        assert_eq!(
+            apply(i64::MAX, &change),
+            Err(FrequencyError::Overflow { line: 7 })
+        );
+    }
+}
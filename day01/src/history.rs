@@ -0,0 +1,370 @@
+// Every frequency reached while going round the changes over and over,
+// described by the frequencies of the first pass and the drift of a whole
+// pass rather than by the values themselves. Each pass reaches the same
+// frequencies as the first, moved along by the drift, so frequencies can
+// only meet if they differ by a multiple of the drift. The first pass is
+// grouped by remainder modulo the drift, and questions about any number
+// of passes are answered from those groups.
+//
+// Time t is the moment after t changes have been applied, so time 0 is the
+// starting frequency of 0 and time n is the end of the first pass.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::str::FromStr;
+
+use crate::change::{apply, parse_changes, Change, FrequencyError};
+use crate::Repeat;
+
+/// A frequency of the first pass and the positions it was reached at
+#[derive(Debug, PartialEq)]
+struct Entry {
+    frequency: i64,
+    indices: Vec<usize>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FrequencyHistory {
+    /// Frequency before each change of the first pass
+    frequencies: Vec<i64>,
+    drift: i64,
+    /// Frequencies of the first pass grouped by remainder modulo the
+    /// drift, or by frequency when there is no drift. Each group is
+    /// sorted in the direction of the drift, so that entries later in the
+    /// group are reached in later passes by the frequencies before them
+    groups: HashMap<i64, Vec<Entry>>,
+}
+
+impl FrequencyHistory {
+    pub fn new(changes: &[Change]) -> Result<FrequencyHistory, FrequencyError> {
+        let mut frequencies = Vec::with_capacity(changes.len());
+        let mut accumulator = 0;
+        for change in changes {
+            frequencies.push(accumulator);
+            accumulator = apply(accumulator, change)?;
+        }
+        let drift = accumulator;
+
+        let mut groups: HashMap<i64, Vec<Entry>> = HashMap::new();
+        let mut sorted: Vec<(i64, usize)> = frequencies.iter().copied().zip(0..).collect();
+        if drift < 0 {
+            sorted.sort_unstable_by_key(|&(frequency, index)| (Reverse(frequency), index));
+        } else {
+            sorted.sort_unstable();
+        }
+
+        for (frequency, index) in sorted {
+            let key = if drift == 0 {
+                frequency
+            } else {
+                frequency.rem_euclid(drift)
+            };
+            let group = groups.entry(key).or_default();
+
+            match group.last_mut() {
+                Some(entry) if entry.frequency == frequency => entry.indices.push(index),
+                _ => group.push(Entry {
+                    frequency,
+                    indices: vec![index],
+                }),
+            }
+        }
+
+        Ok(FrequencyHistory {
+            frequencies,
+            drift,
+            groups,
+        })
+    }
+
+    /// How much the frequency moves over a whole pass
+    pub fn drift(&self) -> i64 {
+        self.drift
+    }
+
+    /// Frequency at the end of the given number of passes
+    fn after(&self, passes: u64) -> i64 {
+        self.drift
+            .saturating_mul(passes.min(i64::MAX as u64) as i64)
+    }
+
+    /// Number of times the frequency is reached within the given number
+    /// of passes, counting the starting frequency, saturating at the limit
+    /// of a u64
+    pub fn visits(&self, frequency: i64, passes: u64) -> u64 {
+        let at_end = (frequency == self.after(passes)) as u64;
+
+        if self.drift == 0 {
+            let count = self
+                .groups
+                .get(&frequency)
+                .map_or(0, |group| group[0].indices.len() as u64);
+            return count.saturating_mul(passes).saturating_add(at_end);
+        }
+
+        let during: u64 = self
+            .groups
+            .get(&frequency.rem_euclid(self.drift))
+            .map_or(0, |group| {
+                group
+                    .iter()
+                    .filter(|entry| {
+                        let behind =
+                            (frequency as i128 - entry.frequency as i128) / self.drift as i128;
+                        (0..passes as i128).contains(&behind)
+                    })
+                    .map(|entry| entry.indices.len() as u64)
+                    .sum()
+            });
+
+        during + at_end
+    }
+
+    /// Lowest and highest frequencies reached within the given number of
+    /// passes, saturating at the limits of an i64
+    pub fn range(&self, passes: u64) -> (i64, i64) {
+        let end = self.after(passes);
+        if passes == 0 {
+            return (end, end);
+        }
+
+        let last = self.after(passes - 1);
+        let lowest = self.frequencies.iter().min().copied().unwrap_or(0);
+        let highest = self.frequencies.iter().max().copied().unwrap_or(0);
+
+        // each frequency moves steadily with the drift, so its extremes
+        // are in the first pass and the last
+        let candidates = [
+            lowest,
+            highest,
+            lowest.saturating_add(last),
+            highest.saturating_add(last),
+            end,
+        ];
+        let min = candidates.iter().min().copied().unwrap_or(0);
+        let max = candidates.iter().max().copied().unwrap_or(0);
+        (min, max)
+    }
+
+    /// Every frequency reached more than once, in the order that each is
+    /// reached for the second time. This never ends if there is a repeat
+    /// and the frequency drifts, as every pass then reaches new repeats
+    pub fn repeats(&self) -> Repeats<'_> {
+        let count = self.frequencies.len() as u128;
+        let mut heap = BinaryHeap::new();
+
+        for group in self.groups.values() {
+            for (position, entry) in group.iter().enumerate() {
+                // the second visit comes from a second index of the same
+                // frequency if there is one, or else from the entry before
+                let first = match (entry.indices.get(1), position.checked_sub(1)) {
+                    (Some(&index), _) => index as u128,
+                    (None, _) if self.drift == 0 => count + entry.indices[0] as u128,
+                    (None, Some(before)) => {
+                        let before = &group[before];
+                        let passes = (entry.frequency as i128 - before.frequency as i128)
+                            / self.drift as i128;
+                        passes as u128 * count + before.indices[0] as u128
+                    }
+                    (None, None) => continue,
+                };
+
+                // the entry keeps being reached twice as it drifts on,
+                // until it reaches the next entry in the group
+                let remaining = match group.get(position + 1) {
+                    _ if self.drift == 0 => Some(1),
+                    Some(next) => Some(
+                        ((next.frequency as i128 - entry.frequency as i128) / self.drift as i128)
+                            as u128,
+                    ),
+                    None => None,
+                };
+
+                heap.push(Reverse(Stream {
+                    time: first,
+                    frequency: entry.frequency,
+                    remaining,
+                }));
+            }
+        }
+
+        Repeats {
+            history: self,
+            heap,
+        }
+    }
+}
+
+impl FromStr for FrequencyHistory {
+    type Err = FrequencyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FrequencyHistory::new(&parse_changes(s)?)
+    }
+}
+
+/// Frequencies which are each reached for a second time one pass after
+/// another, from a frequency of the first pass up to the next in its group
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Stream {
+    time: u128,
+    frequency: i64,
+    remaining: Option<u128>,
+}
+
+pub struct Repeats<'a> {
+    history: &'a FrequencyHistory,
+    heap: BinaryHeap<Reverse<Stream>>,
+}
+
+impl Iterator for Repeats<'_> {
+    type Item = Repeat;
+
+    fn next(&mut self) -> Option<Repeat> {
+        let Reverse(stream) = self.heap.pop()?;
+        let count = self.history.frequencies.len();
+        let repeat = Repeat::at(stream.time, stream.frequency, count);
+
+        let remaining = stream.remaining.map(|r| r - 1);
+        if remaining != Some(0) {
+            if let Some(frequency) = stream.frequency.checked_add(self.history.drift) {
+                self.heap.push(Reverse(Stream {
+                    time: stream.time + count as u128,
+                    frequency,
+                    remaining,
+                }));
+            }
+        }
+
+        Some(repeat)
+    }
+}
+
+#[cfg(test)]
+mod test_history {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    fn history(contents: &str) -> FrequencyHistory {
+        contents.parse().unwrap()
+    }
+
+    /// Every frequency reached, one change at a time
+    fn walk(contents: &str, passes: u64) -> Vec<i64> {
+        let changes = parse_changes(contents).unwrap();
+        let mut frequency = 0;
+        let mut reached = vec![frequency];
+
+        for _ in 0..passes {
+            for change in &changes {
+                frequency += change.value;
+                reached.push(frequency);
+            }
+        }
+        reached
+    }
+
+    /// Repeats found by walking through the given number of passes
+    fn walked_repeats(contents: &str, passes: u64) -> Vec<Repeat> {
+        let count = parse_changes(contents).unwrap().len();
+        let mut seen: HashMap<i64, usize> = HashMap::new();
+        let mut repeats = vec![];
+
+        for (time, frequency) in walk(contents, passes).into_iter().enumerate() {
+            let visits = seen.entry(frequency).or_default();
+            *visits += 1;
+            if *visits == 2 {
+                repeats.push(Repeat::at(time as u128, frequency, count));
+            }
+        }
+        repeats
+    }
+
+    #[rstest(
+        contents,
+        case("+1 -1"),
+        case("+3 +3 +4 -2 -4"),
+        case("-6 +3 +8 +5 -6"),
+        case("+7 +7 -2 -7 -4"),
+        case("+1 +1 -2"),
+        case("+5 -3 +4 -7 +2"),
+        case("-4 +9 -2 -6 +1 +3"),
+        case("+2 +2 -1 -1 -1"),
+        case("+1 +1")
+    )]
+    fn test_repeats(contents: &str) {
+        let expected = walked_repeats(contents, 30);
+        let found: Vec<Repeat> = history(contents).repeats().take(expected.len()).collect();
+
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_repeats_without_drift() {
+        let repeats: Vec<i64> = history("+1 +1 -1 -1")
+            .repeats()
+            .map(|r| r.frequency)
+            .collect();
+
+        // 1 is reached twice in the first pass, the rest in the second
+        assert_eq!(repeats, vec![1, 0, 2]);
+    }
+
+    #[rstest(
+        contents,
+        passes,
+        case("+3 +3 +4 -2 -4", 5),
+        case("-6 +3 +8 +5 -6", 4),
+        case("+1 +1 -1 -1", 3),
+        case("+1 -1 -1 +1", 2),
+        case("+2", 6),
+        case("", 3)
+    )]
+    fn test_visits(contents: &str, passes: u64) {
+        let history = history(contents);
+        let reached = walk(contents, passes);
+
+        for frequency in -20..40 {
+            let expected = reached.iter().filter(|&&f| f == frequency).count() as u64;
+            assert_eq!(history.visits(frequency, passes), expected, "{}", frequency);
+        }
+    }
+
+    #[test]
+    fn test_visits_saturate() {
+        let history = history("+1 -1");
+
+        assert_eq!(history.visits(1, u64::MAX), u64::MAX);
+        assert_eq!(history.visits(0, u64::MAX), u64::MAX);
+        assert_eq!(history.visits(2, u64::MAX), 0);
+    }
+
+    #[rstest(
+        contents,
+        passes,
+        case("+3 +3 +4 -2 -4", 5),
+        case("-6 +3 +8 +5 -6", 4),
+        case("-1 -2 +5", 3),
+        case("+1 -1", 0),
+        case("", 2)
+    )]
+    fn test_range(contents: &str, passes: u64) {
+        let reached = walk(contents, passes);
+        let expected = (
+            *reached.iter().min().unwrap(),
+            *reached.iter().max().unwrap(),
+        );
+
+        assert_eq!(history(contents).range(passes), expected);
+    }
+
+    #[test]
+    fn test_far_passes() {
+        let history = history("+1000000, -999999");
+
+        assert_eq!(history.drift(), 1);
+        assert_eq!(history.visits(1_000_000, u64::MAX), 2);
+        assert_eq!(history.range(1_000_000_000), (0, 1_000_999_999));
+    }
+}
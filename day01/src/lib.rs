@@ -0,0 +1,215 @@
+use change::{apply, parse_changes, FrequencyError};
+use common::explain::Explanation;
+use history::FrequencyHistory;
+
+pub mod change;
+pub mod history;
+
+pub fn explain_resulting_frequency(
+    contents: &str,
+    result: i64,
+) -> Result<Explanation, FrequencyError> {
+    let changes = parse_changes(contents)?;
+
+    Ok(Explanation::new("Part 1: resulting frequency")
+        .fact("changes", changes.len())
+        .fact("increases", changes.iter().filter(|c| c.value > 0).count())
+        .fact("decreases", changes.iter().filter(|c| c.value < 0).count())
+        .fact("frequency", result))
+}
+
+pub fn explain_repeat_frequency(
+    contents: &str,
+    repeat: &Repeat,
+) -> Result<Explanation, FrequencyError> {
+    let history: FrequencyHistory = contents.parse()?;
+    let (lowest, highest) = history.range(repeat.pass + 1);
+
+    Ok(Explanation::new("Part 2: first frequency reached twice")
+        .fact("drift per pass", history.drift())
+        .fact("pass", repeat.pass + 1)
+        .fact("change", repeat.index + 1)
+        .fact("lowest frequency within those passes", lowest)
+        .fact("highest frequency within those passes", highest)
+        .fact("frequency", repeat.frequency))
+}
+
+pub fn get_resulting_frequency(contents: &str) -> Result<i64, FrequencyError> {
+    let mut accumulator = 0;
+
+    for change in parse_changes(contents)? {
+        accumulator = apply(accumulator, &change)?;
+    }
+
+    Ok(accumulator)
+}
+
+/// The first frequency reached twice, and the change which reached it again
+#[derive(Debug, PartialEq)]
+pub struct Repeat {
+    pub frequency: i64,
+    /// Number of complete passes through the changes before this one
+    pub pass: u64,
+    /// Position of the change within the list
+    pub index: usize,
+}
+
+/// Finds the first frequency reached twice without going round the list
+/// until it happens, returning None if no frequency is ever reached twice
+pub fn find_repeat_frequency(contents: &str) -> Result<Option<Repeat>, FrequencyError> {
+    let history: FrequencyHistory = contents.parse()?;
+    Ok(history.repeats().next())
+}
+
+impl Repeat {
+    /// The repeat reached after the given number of changes
+    pub(crate) fn at(time: u128, frequency: i64, count: usize) -> Repeat {
+        let count = count as u128;
+        Repeat {
+            frequency,
+            pass: ((time - 1) / count) as u64,
+            index: ((time - 1) % count) as usize,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_get_resulting_frequency {
+    use super::*;
+    use rstest::*;
+
+    #[rstest(contents, expected,
+        case("+1 +1 +1", 3),
+        case("+1 +1 -2", 0),
+        case("-1 -2 -3", -6),
+        case("+1, +1, -2", 0),
+        case("+2147483647\n+2147483647", 4_294_967_294),
+    )]
+    fn test_provided_examples(contents: &str, expected: i64) -> Result<(), FrequencyError> {
+        let result = get_resulting_frequency(contents)?;
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() {
+        let big = i64::MAX.to_string();
+        let contents = format!("+1\n{}\n-1", big);
+        assert_eq!(
+            get_resulting_frequency(&contents),
+            Err(FrequencyError::Overflow { line: 2 })
+        );
+        assert_eq!(
+            get_resulting_frequency("+1\n+2\n+x"),
+            Err(FrequencyError::Invalid {
+                token: String::from("+x"),
+                line: 3
+            })
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_find_repeat_frequency {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::HashSet;
+
+    /// Goes round the list until a frequency is reached twice
+    fn brute_force(contents: &str, limit: usize) -> Option<Repeat> {
+        let changes: Vec<i64> = contents
+            .split_whitespace()
+            .map(|c| c.parse().unwrap())
+            .collect();
+        let mut visited = HashSet::new();
+        let mut accumulator = 0;
+
+        for pass in 0..limit as u64 {
+            for (index, change) in changes.iter().enumerate() {
+                visited.insert(accumulator);
+                accumulator += change;
+
+                if visited.contains(&accumulator) {
+                    return Some(Repeat {
+                        frequency: accumulator,
+                        pass,
+                        index,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    #[rstest(
+        contents,
+        frequency,
+        pass,
+        index,
+        case("+1 -1", 0, 0, 1),
+        case("+3 +3 +4 -2 -4", 10, 1, 1),
+        case("-6 +3 +8 +5 -6", 5, 2, 1),
+        case("+7 +7 -2 -7 -4", 14, 2, 2),
+        case("+1 +1 -2", 0, 0, 2),
+        case("+2 -1 +1 -1", 2, 0, 2)
+    )]
+    fn test_provided_examples(
+        contents: &str,
+        frequency: i64,
+        pass: u64,
+        index: usize,
+    ) -> Result<(), FrequencyError> {
+        let result = find_repeat_frequency(contents)?;
+        let expected = Repeat {
+            frequency,
+            pass,
+            index,
+        };
+
+        assert_eq!(result, Some(expected));
+        Ok(())
+    }
+
+    #[rstest(contents, case(""), case("+1 +1"), case("-3"), case("+2 +5 -3"))]
+    fn test_no_repeat(contents: &str) -> Result<(), FrequencyError> {
+        assert_eq!(find_repeat_frequency(contents)?, None);
+        Ok(())
+    }
+
+    #[rstest(
+        contents,
+        case("+10 -9"),
+        case("-10 +9"),
+        case("+5 -3 +4 -7 +2"),
+        case("-4 +9 -2 -6 +1 +3"),
+        case("+100 -99 +50 -49")
+    )]
+    fn test_matches_brute_force(contents: &str) -> Result<(), FrequencyError> {
+        assert_eq!(
+            find_repeat_frequency(contents)?,
+            brute_force(contents, 1000)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(find_repeat_frequency("+1 one").is_err());
+    }
+
+    #[test]
+    fn test_large_drift() -> Result<(), FrequencyError> {
+        // wraps around an i32 and takes billions of passes to repeat
+        let result = find_repeat_frequency("+4000000000, -3999999999, +1")?;
+        assert_eq!(
+            result,
+            Some(Repeat {
+                frequency: 4_000_000_000,
+                pass: 1_999_999_999,
+                index: 2,
+            })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+// Checksum of the box ids: for each multiplicity asked for, the number of
+// ids containing some letter exactly that many times, all multiplied
+// together. The puzzle asks for multiplicities of two and three. Letters
+// are grapheme clusters, as they are when comparing ids in part 2.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+use crate::letters;
+
+/// How many times each letter appears in a box id
+#[derive(PartialEq, Debug)]
+pub struct LetterCounts<'a> {
+    pub id: &'a str,
+    counts: BTreeMap<&'a str, usize>,
+}
+
+impl<'a> LetterCounts<'a> {
+    pub fn new(id: &'a str) -> LetterCounts<'a> {
+        let mut counts = BTreeMap::new();
+        for letter in letters(id) {
+            *counts.entry(letter).or_insert(0) += 1;
+        }
+        LetterCounts { id, counts }
+    }
+
+    pub fn count(&self, letter: &str) -> usize {
+        self.counts.get(letter).copied().unwrap_or(0)
+    }
+
+    /// Letters appearing exactly the given number of times, in order
+    pub fn letters_with(&self, multiplicity: usize) -> Vec<&'a str> {
+        self.counts
+            .iter()
+            .filter(|(_, &count)| count == multiplicity)
+            .map(|(&letter, _)| letter)
+            .collect()
+    }
+
+    /// Whether any letter appears exactly the given number of times
+    pub fn has(&self, multiplicity: usize) -> bool {
+        self.counts.values().any(|&count| count == multiplicity)
+    }
+}
+
+impl Display for LetterCounts<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}:", self.id)?;
+        for (letter, count) in &self.counts {
+            write!(f, " {}x{}", letter, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Number of ids with some letter appearing exactly each multiplicity
+#[derive(PartialEq, Debug)]
+pub struct Checksum {
+    /// Number of ids for each multiplicity
+    pub counts: BTreeMap<usize, usize>,
+}
+
+impl Checksum {
+    pub fn new(boxes: &[&str], multiplicities: &[usize]) -> Checksum {
+        let mut counts: BTreeMap<usize, usize> = multiplicities.iter().map(|&m| (m, 0)).collect();
+
+        for id in boxes {
+            let letters = LetterCounts::new(id);
+            for (multiplicity, count) in counts.iter_mut() {
+                *count += letters.has(*multiplicity) as usize;
+            }
+        }
+
+        Checksum { counts }
+    }
+
+    pub fn count(&self, multiplicity: usize) -> usize {
+        self.counts.get(&multiplicity).copied().unwrap_or(0)
+    }
+
+    /// Product of the counts for every multiplicity, or None if it
+    /// does not fit in a u64
+    pub fn value(&self) -> Option<u64> {
+        self.counts.values().try_fold(1u64, |product, &count| {
+            product.checked_mul(u64::try_from(count).ok()?)
+        })
+    }
+}
+
+/// Letters of a box id which count towards the checksum
+#[derive(PartialEq, Debug)]
+pub struct Contribution<'a> {
+    pub id: &'a str,
+    /// Letters appearing exactly each multiplicity, for the multiplicities
+    /// which any letter does
+    pub letters: Vec<(usize, Vec<&'a str>)>,
+}
+
+/// What each id contributes to the checksum, for the ids which do
+pub fn contributions<'a>(boxes: &[&'a str], multiplicities: &[usize]) -> Vec<Contribution<'a>> {
+    boxes
+        .iter()
+        .filter_map(|id| {
+            let letters = LetterCounts::new(id);
+            let found: Vec<(usize, Vec<&'a str>)> = multiplicities
+                .iter()
+                .map(|&m| (m, letters.letters_with(m)))
+                .filter(|(_, letters)| !letters.is_empty())
+                .collect();
+
+            if found.is_empty() {
+                None
+            } else {
+                Some(Contribution { id, letters: found })
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_checksum {
+    use super::*;
+    use rstest::rstest;
+
+    const BOXES: [&str; 7] = [
+        "abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab",
+    ];
+
+    #[rstest(multiplicities, expected,
+        case(vec![2, 3], 12),
+        case(vec![3, 2, 3], 12),
+        case(vec![2], 4),
+        case(vec![1, 2, 3], 6 * 4 * 3),
+        case(vec![4], 0),
+        case(vec![], 1),
+    )]
+    fn test_value(multiplicities: Vec<usize>, expected: u64) {
+        assert_eq!(
+            Checksum::new(&BOXES, &multiplicities).value(),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn test_value_overflow() {
+        let checksum = Checksum {
+            counts: BTreeMap::from([(2, usize::MAX), (3, 2)]),
+        };
+
+        assert_eq!(checksum.value(), None);
+    }
+
+    #[test]
+    fn test_counts() {
+        let checksum = Checksum::new(&BOXES, &[2, 3, 4]);
+
+        assert_eq!(checksum.count(2), 4);
+        assert_eq!(checksum.count(3), 3);
+        assert_eq!(checksum.count(4), 0);
+        assert_eq!(checksum.count(5), 0);
+    }
+
+    #[test]
+    fn test_letter_counts() {
+        let letters = LetterCounts::new("bababc");
+
+        assert_eq!(letters.count("b"), 3);
+        assert_eq!(letters.count("z"), 0);
+        assert_eq!(letters.letters_with(2), vec!["a"]);
+        assert_eq!(letters.letters_with(3), vec!["b"]);
+        assert_eq!(letters.to_string(), "bababc: ax2 bx3 cx1");
+    }
+
+    #[test]
+    fn test_graphemes() {
+        // e followed by a combining acute accent is a single letter
+        let id = "e\u{301}ae\u{301}";
+        let letters = LetterCounts::new(id);
+
+        assert_eq!(letters.count("e\u{301}"), 2);
+        assert_eq!(letters.count("e"), 0);
+        assert!(!letters.has(3));
+        assert_eq!(Checksum::new(&[id], &[2]).value(), Some(1));
+    }
+
+    #[test]
+    fn test_contributions() {
+        let found = contributions(&BOXES[..3], &[2, 3]);
+
+        assert_eq!(
+            found,
+            vec![
+                Contribution {
+                    id: "bababc",
+                    letters: vec![(2, vec!["a"]), (3, vec!["b"])]
+                },
+                Contribution {
+                    id: "abbcde",
+                    letters: vec![(2, vec!["b"])]
+                },
+            ]
+        );
+    }
+}
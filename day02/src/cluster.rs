@@ -0,0 +1,611 @@
+// Groups box ids which are within some number of edits of each other,
+// generalising part 2 beyond ids differing by a single letter. Ids are
+// kept in a BK-tree, which relies on the triangle inequality so that a
+// search only visits the children whose distance from their parent could
+// hold an id close enough to the one searched for.
+// https://en.wikipedia.org/wiki/BK-tree
+//
+// Clusters are built by complete linkage: the two clusters whose furthest
+// apart ids are closest are merged first, and only while those ids are
+// within the threshold, so every pair of ids in a cluster is close.
+// https://en.wikipedia.org/wiki/Complete-linkage_clustering
+
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap};
+
+use common::cli::OptionsError;
+
+use crate::similar::BoxIdError;
+use crate::{common_letters, letters};
+
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub enum Distance {
+    /// Positions at which the letters differ. Ids must be of equal length
+    #[default]
+    Hamming,
+    /// Letters inserted, removed or replaced to turn one id into the other
+    Levenshtein,
+}
+
+impl Distance {
+    pub fn between(&self, a: &str, b: &str) -> Result<usize, BoxIdError> {
+        match self {
+            Distance::Hamming => {
+                let expected = letters(a).count();
+                let found = letters(b).count();
+                if found != expected {
+                    return Err(BoxIdError::UnequalLength {
+                        id: String::from(b),
+                        expected,
+                        found,
+                    });
+                }
+                Ok(letters(a).zip(letters(b)).filter(|(x, y)| x != y).count())
+            }
+            Distance::Levenshtein => Ok(levenshtein(a, b)),
+        }
+    }
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<&str> = letters(b).collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, x) in letters(a).enumerate() {
+        current[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let replace = previous[j] + (x != *y) as usize;
+            current[j + 1] = replace.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Longest run of letters which both ids have in the same order, though
+/// not necessarily at the same positions
+fn longest_common<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<&'a str> {
+    // lengths[i][j] is the length of the longest common letters of a[i..] and b[j..]
+    let mut lengths = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = if a[i] == b[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut common = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            common.push(a[i]);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    common
+}
+
+/// Letters which every id has in the same order, found by aligning each id
+/// in turn with the letters kept so far. For more than two ids this keeps
+/// letters common to all of them, though not always as many as possible
+fn aligned_letters(boxes: &[&str]) -> String {
+    let mut ids = boxes.iter().map(|id| letters(id).collect::<Vec<&str>>());
+    let first = ids.next().unwrap_or_default();
+
+    ids.fold(first, |kept, id| longest_common(&kept, &id))
+        .concat()
+}
+
+/// Nodes are kept in the order their ids were inserted
+struct Node<'a> {
+    id: &'a str,
+    /// Child node for each distance from this node
+    children: HashMap<usize, usize>,
+}
+
+pub struct BkTree<'a> {
+    distance: Distance,
+    nodes: Vec<Node<'a>>,
+}
+
+impl<'a> BkTree<'a> {
+    pub fn new(distance: Distance) -> BkTree<'a> {
+        BkTree {
+            distance,
+            nodes: vec![],
+        }
+    }
+
+    pub fn insert(&mut self, id: &'a str) -> Result<(), BoxIdError> {
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            id,
+            children: HashMap::new(),
+        });
+
+        let mut current = 0;
+        while current != index {
+            let d = self.distance.between(self.nodes[current].id, id)?;
+            current = *self.nodes[current].children.entry(d).or_insert(index);
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Positions of the ids within the given distance of the id, in the
+    /// order they were inserted
+    pub fn within(&self, id: &str, limit: usize) -> Result<Vec<usize>, BoxIdError> {
+        let mut found = vec![];
+        let mut pending = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+
+        while let Some(current) = pending.pop() {
+            let node = &self.nodes[current];
+            let d = self.distance.between(node.id, id)?;
+            if d <= limit {
+                found.push(current);
+            }
+
+            pending.extend(
+                node.children
+                    .iter()
+                    .filter(|(&child, _)| d.abs_diff(child) <= limit)
+                    .map(|(_, &next)| next),
+            );
+        }
+
+        found.sort_unstable();
+        Ok(found)
+    }
+}
+
+/// Box ids which are all within the threshold of each other
+#[derive(PartialEq, Debug)]
+pub struct Cluster<'a> {
+    /// In the order they appear in the list
+    pub ids: Vec<&'a str>,
+    /// How the ids were compared
+    pub distance: Distance,
+}
+
+impl Cluster<'_> {
+    /// Letters which every id has in common. Ids compared letter by letter
+    /// keep those at the same position, while ids which may differ in length
+    /// are aligned first
+    pub fn common(&self) -> Result<String, BoxIdError> {
+        match self.distance {
+            Distance::Hamming => common_letters(&self.ids),
+            Distance::Levenshtein => Ok(aligned_letters(&self.ids)),
+        }
+    }
+}
+
+/// Groups the ids into clusters of two or more, leaving out ids which
+/// are not within the threshold of any other
+pub fn find_clusters<'a>(
+    boxes: &[&'a str],
+    threshold: usize,
+    distance: Distance,
+) -> Result<Vec<Cluster<'a>>, BoxIdError> {
+    let mut tree = BkTree::new(distance);
+    for id in boxes {
+        tree.insert(id)?;
+    }
+
+    // ids within the threshold of each id, along with how far apart they are
+    let mut close: Vec<HashMap<usize, usize>> = Vec::with_capacity(boxes.len());
+    for id in boxes {
+        let mut near = HashMap::new();
+        for j in tree.within(id, threshold)? {
+            near.insert(j, distance.between(id, boxes[j])?);
+        }
+        close.push(near);
+    }
+
+    // distance between the furthest apart ids of two clusters, if every
+    // pair of them is within the threshold
+    let linkage = |a: &[usize], b: &[usize]| -> Option<usize> {
+        a.iter()
+            .flat_map(|&i| b.iter().map(move |&j| (i, j)))
+            .map(|(i, j)| close[i].get(&j).copied())
+            .try_fold(0, |furthest, d| d.map(|d| furthest.max(d)))
+    };
+
+    // every cluster made so far, taken out once merged into another
+    let mut clusters: Vec<Option<Vec<usize>>> = (0..boxes.len()).map(|i| Some(vec![i])).collect();
+    let mut owner: Vec<usize> = (0..boxes.len()).collect();
+    let mut merges = BinaryHeap::new();
+    for (i, near) in close.iter().enumerate() {
+        for (&j, &d) in near.iter().filter(|(&j, _)| i < j) {
+            merges.push(Reverse((d, i, j)));
+        }
+    }
+
+    while let Some(Reverse((_, a, b))) = merges.pop() {
+        if clusters[a].is_none() || clusters[b].is_none() {
+            continue;
+        }
+        let mut merged = clusters[a].take().unwrap_or_default();
+        merged.extend(clusters[b].take().unwrap_or_default());
+        merged.sort_unstable();
+
+        let position = clusters.len();
+        for &i in &merged {
+            owner[i] = position;
+        }
+
+        let neighbours: BTreeSet<usize> = merged
+            .iter()
+            .flat_map(|&i| close[i].keys().map(|&j| owner[j]))
+            .filter(|&other| other != position)
+            .collect();
+        for other in neighbours {
+            if let Some(d) = clusters[other].as_deref().and_then(|o| linkage(&merged, o)) {
+                merges.push(Reverse((d, other, position)));
+            }
+        }
+        clusters.push(Some(merged));
+    }
+
+    let mut clusters: Vec<Vec<usize>> = clusters
+        .into_iter()
+        .flatten()
+        .filter(|cluster| cluster.len() > 1)
+        .collect();
+    clusters.sort_unstable();
+
+    Ok(clusters
+        .into_iter()
+        .map(|cluster| Cluster {
+            ids: cluster.iter().map(|&i| boxes[i]).collect(),
+            distance,
+        })
+        .collect())
+}
+
+/// Options for clustering the ids, taken out of the command line before
+/// the options understood by every day
+#[derive(Debug, Default, PartialEq)]
+pub struct ClusterOptions {
+    /// Print the clusters of ids within this distance of each other
+    pub threshold: Option<usize>,
+    /// How far apart two ids are
+    pub distance: Distance,
+}
+
+impl ClusterOptions {
+    /// The cluster options and the arguments left over
+    pub fn parse<I, S>(args: I) -> Result<(ClusterOptions, Vec<String>), OptionsError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut options = ClusterOptions::default();
+        let mut chosen = false;
+        let mut rest = vec![];
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            let mut value = || {
+                args.next()
+                    .map(|v| String::from(v.as_ref()))
+                    .ok_or_else(|| OptionsError::MissingValue(String::from(arg)))
+            };
+
+            match arg {
+                "--cluster" => {
+                    let threshold = value()?;
+                    match threshold.parse() {
+                        Ok(number) => options.threshold = Some(number),
+                        _ => return Err(OptionsError::InvalidValue(String::from(arg), threshold)),
+                    }
+                }
+                "--distance" => {
+                    let distance = value()?;
+                    options.distance = match distance.as_str() {
+                        "hamming" => Distance::Hamming,
+                        "levenshtein" => Distance::Levenshtein,
+                        _ => return Err(OptionsError::InvalidValue(String::from(arg), distance)),
+                    };
+                    chosen = true;
+                }
+                other => rest.push(String::from(other)),
+            }
+        }
+
+        if chosen && options.threshold.is_none() {
+            return Err(OptionsError::Requires(
+                String::from("--distance"),
+                String::from("--cluster"),
+            ));
+        }
+        Ok((options, rest))
+    }
+}
+
+#[cfg(test)]
+mod test_distance {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(
+        a,
+        b,
+        hamming,
+        levenshtein,
+        case("abcde", "abcde", 0, 0),
+        case("abcde", "axcye", 2, 2),
+        case("fghij", "fguij", 1, 1),
+        case("abcde", "bcdea", 5, 2),
+        case("kitten", "sittin", 2, 2)
+    )]
+    fn test_between(a: &str, b: &str, hamming: usize, levenshtein: usize) {
+        assert_eq!(Distance::Hamming.between(a, b), Ok(hamming));
+        assert_eq!(Distance::Levenshtein.between(a, b), Ok(levenshtein));
+    }
+
+    #[test]
+    fn test_graphemes() {
+        // e followed by a combining acute accent is a single letter
+        assert_eq!(Distance::Hamming.between("e\u{301}bc", "ebc"), Ok(1));
+        assert_eq!(Distance::Levenshtein.between("e\u{301}bc", "bc"), Ok(1));
+    }
+
+    #[test]
+    fn test_levenshtein_lengths() {
+        assert_eq!(Distance::Levenshtein.between("", "abc"), Ok(3));
+        assert_eq!(Distance::Levenshtein.between("abcde", "abde"), Ok(1));
+        assert_eq!(Distance::Levenshtein.between("sitting", "kitten"), Ok(3));
+    }
+
+    #[test]
+    fn test_hamming_lengths() {
+        assert_eq!(
+            Distance::Hamming.between("abcde", "abde"),
+            Err(BoxIdError::UnequalLength {
+                id: String::from("abde"),
+                expected: 5,
+                found: 4
+            })
+        );
+    }
+
+    #[rstest(
+        boxes,
+        expected,
+        case(vec!["abcde", "abde"], "abde"),
+        case(vec!["abcde", "xabcde"], "abcde"),
+        case(vec!["kitten", "sitting"], "ittn"),
+        case(vec!["abcde", "abde", "bcdef"], "bde"),
+        case(vec!["e\u{301}bc", "bc"], "bc"),
+        case(vec!["abc"], "abc"),
+        case(vec![], "")
+    )]
+    fn test_aligned_letters(boxes: Vec<&str>, expected: &str) {
+        assert_eq!(aligned_letters(&boxes), expected);
+    }
+}
+
+#[cfg(test)]
+mod test_clusters {
+    use super::*;
+    use crate::compare_box_ids;
+
+    const BOXES: [&str; 7] = [
+        "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+    ];
+
+    fn ids<'a>(clusters: &[Cluster<'a>]) -> Vec<Vec<&'a str>> {
+        clusters.iter().map(|c| c.ids.clone()).collect()
+    }
+
+    #[test]
+    fn test_within() -> Result<(), BoxIdError> {
+        let mut tree = BkTree::new(Distance::Hamming);
+        for id in &BOXES {
+            tree.insert(id)?;
+        }
+
+        assert_eq!(tree.len(), 7);
+        assert_eq!(tree.within("fghij", 1)?, vec![1, 4]);
+        assert_eq!(tree.within("abcde", 2)?, vec![0, 5]);
+        assert_eq!(tree.within("zzzzz", 3)?, Vec::<usize>::new());
+        assert_eq!(tree.within("klmno", 5)?.len(), 7);
+        assert!(tree.within("abcd", 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_within_matches_every_pair() -> Result<(), BoxIdError> {
+        let boxes = [
+            "abcd", "abce", "xbcd", "abcd", "axyd", "zzzz", "abzd", "wxyz", "azcz",
+        ];
+        let mut tree = BkTree::new(Distance::Levenshtein);
+        for id in &boxes {
+            tree.insert(id)?;
+        }
+
+        for id in &boxes {
+            for limit in 0..4 {
+                let expected: Vec<usize> = (0..boxes.len())
+                    .filter(|&j| levenshtein(id, boxes[j]) <= limit)
+                    .collect();
+                assert_eq!(tree.within(id, limit)?, expected);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_letter() -> Result<(), BoxIdError> {
+        let clusters = find_clusters(&BOXES, 1, Distance::Hamming)?;
+
+        assert_eq!(ids(&clusters), vec![vec!["fghij", "fguij"]]);
+        assert_eq!(clusters[0].common()?, compare_box_ids("fghij", "fguij")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_two_letters() -> Result<(), BoxIdError> {
+        let clusters = find_clusters(&BOXES, 2, Distance::Hamming)?;
+
+        assert_eq!(
+            ids(&clusters),
+            vec![vec!["abcde", "axcye"], vec!["fghij", "fguij"]]
+        );
+        assert_eq!(clusters[0].common()?, "ace");
+        Ok(())
+    }
+
+    #[test]
+    fn test_chained() -> Result<(), BoxIdError> {
+        // aabb is close to aaab but two letters from aaaa, so it cannot
+        // join the cluster they form
+        let clusters = find_clusters(&["aaaa", "zzzz", "aaab", "aabb"], 1, Distance::Hamming)?;
+
+        assert_eq!(ids(&clusters), vec![vec!["aaaa", "aaab"]]);
+        assert_eq!(clusters[0].common()?, "aaa");
+        Ok(())
+    }
+
+    #[test]
+    fn test_closest_merged_first() -> Result<(), BoxIdError> {
+        // the two copies of aaab are merged first, then aaaa joins them
+        // ahead of aabb as it comes first in the list
+        let clusters = find_clusters(&["aaaa", "aaab", "aabb", "aaab"], 1, Distance::Hamming)?;
+
+        assert_eq!(ids(&clusters), vec![vec!["aaaa", "aaab", "aaab"]]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mutually_close() -> Result<(), BoxIdError> {
+        let boxes = [
+            "abcd", "abce", "xbcd", "abcd", "axyd", "zzzz", "abzd", "wxyz", "azcz", "abzz",
+        ];
+
+        for threshold in 0..4 {
+            for distance in [Distance::Hamming, Distance::Levenshtein] {
+                for cluster in find_clusters(&boxes, threshold, distance)? {
+                    for a in &cluster.ids {
+                        for b in &cluster.ids {
+                            assert!(distance.between(a, b)? <= threshold, "{} {}", a, b);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_levenshtein() -> Result<(), BoxIdError> {
+        let clusters = find_clusters(
+            &["abcde", "fghij", "abde", "xabcde"],
+            1,
+            Distance::Levenshtein,
+        )?;
+
+        // abde and xabcde are each one edit from abcde but two apart
+        assert_eq!(ids(&clusters), vec![vec!["abcde", "abde"]]);
+        assert_eq!(clusters[0].common()?, "abde");
+        Ok(())
+    }
+
+    #[test]
+    fn test_levenshtein_common() -> Result<(), BoxIdError> {
+        let clusters = find_clusters(&["abcde", "abde", "bcde", "zzzz"], 2, Distance::Levenshtein)?;
+
+        assert_eq!(ids(&clusters), vec![vec!["abcde", "abde", "bcde"]]);
+        assert_eq!(clusters[0].common()?, "bde");
+        Ok(())
+    }
+
+    #[test]
+    fn test_unequal_length() {
+        assert!(find_clusters(&["abcde", "abde"], 1, Distance::Hamming).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_cluster_options {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let (options, rest) = ClusterOptions::parse(vec![
+            "--explain",
+            "--cluster",
+            "2",
+            "--distance",
+            "levenshtein",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            options,
+            ClusterOptions {
+                threshold: Some(2),
+                distance: Distance::Levenshtein,
+            }
+        );
+        assert_eq!(rest, vec!["--explain"]);
+        assert_eq!(
+            ClusterOptions::parse(vec!["--cluster", "1"]).map(|(options, _)| options),
+            Ok(ClusterOptions {
+                threshold: Some(1),
+                distance: Distance::Hamming,
+            })
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            ClusterOptions::parse(vec!["--cluster"]),
+            Err(OptionsError::MissingValue(String::from("--cluster")))
+        );
+        assert_eq!(
+            ClusterOptions::parse(vec!["--cluster", "-1"]),
+            Err(OptionsError::InvalidValue(
+                String::from("--cluster"),
+                String::from("-1")
+            ))
+        );
+        assert_eq!(
+            ClusterOptions::parse(vec!["--cluster", "1", "--distance", "manhattan"]),
+            Err(OptionsError::InvalidValue(
+                String::from("--distance"),
+                String::from("manhattan")
+            ))
+        );
+        assert_eq!(
+            ClusterOptions::parse(vec!["--distance", "hamming"]),
+            Err(OptionsError::Requires(
+                String::from("--distance"),
+                String::from("--cluster")
+            ))
+        );
+    }
+}
@@ -0,0 +1,179 @@
+// https://adventofcode.com/2018/day/2
+
+use checksum::{contributions, Checksum, LetterCounts};
+use common::explain::Explanation;
+use similar::{check_lengths, find_similar_pairs, BoxIdError, SimilarPair};
+use unicode_segmentation::{Graphemes, UnicodeSegmentation};
+
+pub mod checksum;
+pub mod cluster;
+pub mod similar;
+
+/// Letters of a box id. Each grapheme cluster is one letter, so an accented
+/// letter written as a base letter and a combining mark counts once in the
+/// checksum and is compared as a whole when looking for similar ids
+pub fn letters(id: &str) -> Graphemes<'_> {
+    id.graphemes(true)
+}
+
+/// The checksum, or None if it does not fit in a u64
+pub fn get_solution_1(boxes: &[&str]) -> Option<u64> {
+    Checksum::new(boxes, &[2, 3]).value()
+}
+
+/// Number of box ids containing a letter exactly twice and
+/// the number containing a letter exactly three times
+pub fn count_box_ids(boxes: &[&str]) -> (usize, usize) {
+    let checksum = Checksum::new(boxes, &[2, 3]);
+
+    (checksum.count(2), checksum.count(3))
+}
+
+pub fn explain_solution_1(boxes: &[&str]) -> Explanation {
+    let (twice, thrice) = count_box_ids(boxes);
+
+    Explanation::new("Part 1: checksum")
+        .fact("box ids", boxes.len())
+        .fact("ids counted", contributions(boxes, &[2, 3]).len())
+        .fact("ids with a letter exactly twice", twice)
+        .fact("ids with a letter exactly three times", thrice)
+        .fact("checksum", format!("{} * {}", twice, thrice))
+}
+
+pub fn check_box_id(box_id: &str) -> (bool, bool) {
+    let letters = LetterCounts::new(box_id);
+
+    (letters.has(2), letters.has(3))
+}
+
+/// Letters which the two ids have in common at the same positions
+pub fn compare_box_ids(box1: &str, box2: &str) -> Result<String, BoxIdError> {
+    common_letters(&[box1, box2])
+}
+
+/// Letters which every id has in common at the same positions
+pub fn common_letters(boxes: &[&str]) -> Result<String, BoxIdError> {
+    check_lengths(boxes)?;
+    let ids: Vec<Vec<&str>> = boxes.iter().map(|id| letters(id).collect()).collect();
+    let first = ids.first().map_or(&[][..], |id| &id[..]);
+
+    Ok(first
+        .iter()
+        .enumerate()
+        .filter(|(i, c)| ids.iter().all(|id| id[*i] == **c))
+        .map(|(_, c)| *c)
+        .collect())
+}
+
+/// Letters in common between the first two box ids which differ by
+/// exactly one character
+pub fn find_similar_box_id(boxes: &[&str]) -> Result<Option<String>, BoxIdError> {
+    let pairs = find_similar_pairs(boxes)?;
+    Ok(pairs.first().map(SimilarPair::common))
+}
+
+pub fn explain_similar_box_id(boxes: &[&str]) -> Option<Explanation> {
+    let pairs = find_similar_pairs(boxes).ok()?;
+    let pair = pairs.first()?;
+
+    Some(
+        Explanation::new("Part 2: letters common to the two similar box ids")
+            .fact("similar pairs", pairs.len())
+            .fact("first box id", pair.first)
+            .fact("second box id", pair.second)
+            .fact("differing position", pair.position)
+            .fact("common letters", pair.common()),
+    )
+}
+
+#[cfg(test)]
+mod test_check_box_id {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(input, expected,
+        case("abcdef", (false, false)),
+        case("bababc", (true, true)),
+        case("abbcde", (true, false)),
+        case("abcccd", (false, true)),
+        case("aabcdd", (true, false)),
+        case("abcdee", (true, false)),
+        case("ababab", (false, true)),
+    )]
+    fn test_cases(input: &str, expected: (bool, bool)) {
+        let result = check_box_id(input);
+        assert_eq!(result, expected);
+    }
+}
+
+#[cfg(test)]
+mod test_find_similar_box_id {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(find_similar_box_id(&[]), Ok(None));
+    }
+
+    #[test]
+    fn test_no_similar() {
+        assert_eq!(find_similar_box_id(&["abdef", "podid", "12345"]), Ok(None));
+    }
+
+    #[test]
+    fn test_finds_similar() {
+        let input = vec![
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+        ];
+        assert_eq!(find_similar_box_id(&input), Ok(Some(String::from("fgij"))));
+    }
+
+    #[test]
+    fn test_unequal_length() {
+        assert!(find_similar_box_id(&["abcde", "abcd"]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_compare_box_ids {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(
+        box1,
+        box2,
+        expected,
+        case("abcde", "abcde", "abcde"),
+        case("abcde", "axcye", "ace"),
+        case("fghij", "fguij", "fgij")
+    )]
+    fn test_cases(box1: &str, box2: &str, expected: &str) {
+        assert_eq!(compare_box_ids(box1, box2), Ok(String::from(expected)));
+    }
+
+    #[test]
+    fn test_unequal_length() {
+        assert_eq!(
+            compare_box_ids("abcde", "abc"),
+            Err(BoxIdError::UnequalLength {
+                id: String::from("abc"),
+                expected: 5,
+                found: 3
+            })
+        );
+    }
+
+    #[rstest(
+        boxes,
+        expected,
+        case(vec!["abcd", "xbcd", "abcz"], "bc"),
+        case(vec!["abcd", "abcd", "abcd"], "abcd"),
+        case(vec!["abcd"], "abcd"),
+        case(vec!["e\u{301}bc", "e\u{301}xc"], "e\u{301}c"),
+        case(vec!["e\u{301}bc", "ebc"], "bc"),
+        case(vec![], "")
+    )]
+    fn test_common_letters(boxes: Vec<&str>, expected: &str) {
+        assert_eq!(common_letters(&boxes), Ok(String::from(expected)));
+    }
+}
@@ -0,0 +1,272 @@
+// Finds box ids which differ at exactly one position without comparing
+// every pair. Each id is hashed once, and taking out the letter at a
+// position from that hash gives the hash of the id with a wildcard there.
+// Two ids differing only at that position have the same wildcard hash, so
+// only ids sharing a bucket need to be compared. Repeated ids are bucketed
+// once, which keeps this linear in the number of ids and their length plus
+// the number of pairs found. Candidates are still compared letter by
+// letter, so hash collisions never produce a wrong pair. Letters are
+// grapheme clusters rather than chars.
+
+use std::collections::HashMap;
+
+use crate::letters;
+
+/// Multiplier of the polynomial hash. Any odd number works, this one is
+/// large so that the letters are spread over the whole of the hash
+const BASE: u64 = 0x100_0000_01b3;
+
+#[derive(PartialEq, Debug)]
+pub enum BoxIdError {
+    UnequalLength {
+        id: String,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl std::fmt::Display for BoxIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BoxIdError::UnequalLength {
+                id,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Box id {:?} has {} letters, expected {}",
+                id, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BoxIdError {}
+
+/// Two box ids which differ at exactly one position
+#[derive(PartialEq, Debug)]
+pub struct SimilarPair<'a> {
+    pub first: &'a str,
+    pub second: &'a str,
+    /// Position of the letter which differs, counted in grapheme clusters
+    pub position: usize,
+}
+
+impl SimilarPair<'_> {
+    /// Letters which the two ids have in common
+    pub fn common(&self) -> String {
+        letters(self.first)
+            .enumerate()
+            .filter(|(i, _)| *i != self.position)
+            .map(|(_, c)| c)
+            .collect()
+    }
+}
+
+/// Checks that every id has as many letters as the first
+pub fn check_lengths(boxes: &[&str]) -> Result<usize, BoxIdError> {
+    let expected = boxes.first().map_or(0, |id| letters(id).count());
+
+    for id in boxes {
+        let found = letters(id).count();
+        if found != expected {
+            return Err(BoxIdError::UnequalLength {
+                id: String::from(*id),
+                expected,
+                found,
+            });
+        }
+    }
+    Ok(expected)
+}
+
+/// Every pair of ids which differ at exactly one position, ordered by
+/// where the first and then the second of the pair appear in the list
+pub fn find_similar_pairs<'a>(boxes: &[&'a str]) -> Result<Vec<SimilarPair<'a>>, BoxIdError> {
+    let length = check_lengths(boxes)?;
+
+    // positions in the list of each distinct id, since an id never pairs
+    // with a copy of itself
+    let mut ids: Vec<Vec<&str>> = vec![];
+    let mut positions: Vec<Vec<usize>> = vec![];
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for (index, id) in boxes.iter().enumerate() {
+        let distinct = *seen.entry(id).or_insert_with(|| {
+            ids.push(letters(id).collect());
+            positions.push(vec![]);
+            ids.len() - 1
+        });
+        positions[distinct].push(index);
+    }
+
+    let mut powers = Vec::with_capacity(length);
+    let mut power: u64 = 1;
+    for _ in 0..length {
+        powers.push(power);
+        power = power.wrapping_mul(BASE);
+    }
+
+    let hashes: Vec<u64> = ids
+        .iter()
+        .map(|id| {
+            id.iter().zip(&powers).fold(0u64, |hash, (letter, &p)| {
+                hash.wrapping_add(p.wrapping_mul(value(letter)))
+            })
+        })
+        .collect();
+
+    let mut pairs = vec![];
+    let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+
+    for position in 0..length {
+        buckets.clear();
+        for (index, id) in ids.iter().enumerate() {
+            let wildcard =
+                hashes[index].wrapping_sub(powers[position].wrapping_mul(value(id[position])));
+            buckets.entry(wildcard).or_default().push(index);
+        }
+
+        for bucket in buckets.values().filter(|b| b.len() > 1) {
+            for (n, &i) in bucket.iter().enumerate() {
+                for &j in &bucket[n + 1..] {
+                    if differs_only_at(&ids[i], &ids[j], position) {
+                        for &a in &positions[i] {
+                            for &b in &positions[j] {
+                                pairs.push((a.min(b), a.max(b), position));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pairs.sort_unstable();
+    Ok(pairs
+        .into_iter()
+        .map(|(i, j, position)| SimilarPair {
+            first: boxes[i],
+            second: boxes[j],
+            position,
+        })
+        .collect())
+}
+
+/// Number standing for a letter in the hash
+fn value(letter: &str) -> u64 {
+    letter.chars().fold(0u64, |value, c| {
+        value.wrapping_mul(BASE).wrapping_add(c as u64)
+    })
+}
+
+fn differs_only_at(a: &[&str], b: &[&str], position: usize) -> bool {
+    a[position] != b[position]
+        && a.iter()
+            .zip(b)
+            .enumerate()
+            .all(|(i, (x, y))| i == position || x == y)
+}
+
+#[cfg(test)]
+mod test_find_similar_pairs {
+    use super::*;
+
+    /// Compares every pair, as the solution did originally
+    fn every_pair<'a>(boxes: &[&'a str]) -> Vec<SimilarPair<'a>> {
+        let mut pairs = vec![];
+        for (i, first) in boxes.iter().enumerate() {
+            for second in &boxes[i + 1..] {
+                let differences: Vec<usize> = first
+                    .chars()
+                    .zip(second.chars())
+                    .enumerate()
+                    .filter(|(_, (a, b))| a != b)
+                    .map(|(i, _)| i)
+                    .collect();
+
+                if let [position] = differences[..] {
+                    pairs.push(SimilarPair {
+                        first,
+                        second,
+                        position,
+                    });
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn test_example() {
+        let boxes = [
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+        ];
+        let pairs = find_similar_pairs(&boxes).unwrap();
+
+        assert_eq!(
+            pairs,
+            vec![SimilarPair {
+                first: "fghij",
+                second: "fguij",
+                position: 2
+            }]
+        );
+        assert_eq!(pairs[0].common(), "fgij");
+    }
+
+    #[test]
+    fn test_every_pair() {
+        let boxes = [
+            "abcd", "abce", "abcf", "xbcd", "abcd", "axcd", "zzzz", "abzd", "ébcd",
+        ];
+        assert_eq!(find_similar_pairs(&boxes).unwrap(), every_pair(&boxes));
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let boxes = [
+            "abcd", "abce", "abcd", "abcd", "xbcd", "abce", "zzzz", "zzzz", "abcd",
+        ];
+        assert_eq!(find_similar_pairs(&boxes).unwrap(), every_pair(&boxes));
+    }
+
+    #[test]
+    fn test_graphemes() {
+        // e followed by a combining acute accent is a single letter
+        let pairs = find_similar_pairs(&["e\u{301}bcd", "abcd"]).unwrap();
+
+        assert_eq!(
+            pairs,
+            vec![SimilarPair {
+                first: "e\u{301}bcd",
+                second: "abcd",
+                position: 0
+            }]
+        );
+        assert_eq!(pairs[0].common(), "bcd");
+    }
+
+    #[test]
+    fn test_identical() {
+        // the same id twice is not a pair differing by one letter
+        assert_eq!(find_similar_pairs(&["abc", "abc"]).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_unequal_length() {
+        assert_eq!(
+            find_similar_pairs(&["abcde", "abcd", "abcdf"]),
+            Err(BoxIdError::UnequalLength {
+                id: String::from("abcd"),
+                expected: 5,
+                found: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(find_similar_pairs(&[]).unwrap(), vec![]);
+        assert_eq!(find_similar_pairs(&["", ""]).unwrap(), vec![]);
+    }
+}
@@ -0,0 +1,394 @@
+// Renders the fabric as a PPM or PNG image with a pixel for each square
+// inch, coloured by how many claims cover it.
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use common::cli::OptionsError;
+
+use crate::rect::Rect;
+use crate::Fabric;
+
+pub type Colour = [u8; 3];
+
+pub const UNCLAIMED: Colour = [24, 24, 32];
+pub const CLAIMED: Colour = [96, 96, 112];
+pub const INTACT: Colour = [64, 200, 96];
+pub const OUTLINE: Colour = [64, 160, 255];
+
+/// Most pixels in an image, as claims far apart would otherwise need
+/// more memory than there is
+pub const MAX_PIXELS: i64 = 4096 * 4096;
+
+#[derive(Debug)]
+pub enum HeatmapError {
+    Io(io::Error),
+    Png(png::EncodingError),
+    UnknownFormat(PathBuf),
+    TooLarge { width: i64, height: i64 },
+}
+
+impl Display for HeatmapError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            HeatmapError::Io(err) => write!(f, "{}", err),
+            HeatmapError::Png(err) => write!(f, "{}", err),
+            HeatmapError::UnknownFormat(path) => write!(
+                f,
+                "Unknown image format for {}, expected .ppm or .png",
+                path.display()
+            ),
+            HeatmapError::TooLarge { width, height } => write!(
+                f,
+                "An image of {}x{} is too large, at most {} pixels are allowed",
+                width, height, MAX_PIXELS
+            ),
+        }
+    }
+}
+
+impl Error for HeatmapError {}
+
+impl From<io::Error> for HeatmapError {
+    fn from(err: io::Error) -> Self {
+        HeatmapError::Io(err)
+    }
+}
+
+impl From<png::EncodingError> for HeatmapError {
+    fn from(err: png::EncodingError) -> Self {
+        HeatmapError::Png(err)
+    }
+}
+
+/// Colour for a square inch covered by the given number of claims
+pub fn heat(claims: i32, most: i32) -> Colour {
+    match claims {
+        c if c <= 0 => UNCLAIMED,
+        1 => CLAIMED,
+        c => {
+            // from yellow for two claims to red for the most claims anywhere
+            let spread = (most - 2).max(1) as f64;
+            let t = ((c - 2) as f64 / spread).min(1.0);
+            [255, (224.0 * (1.0 - t)) as u8, 32]
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Heatmap {
+    /// Position of the square inch in the top left corner
+    origin: (i32, i32),
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Colour>,
+}
+
+impl Heatmap {
+    /// Fails when the claims cover an area of more than MAX_PIXELS
+    pub fn new(fabric: &Fabric) -> Result<Heatmap, HeatmapError> {
+        let (left, top, right, bottom) = fabric.bounds();
+        let width = right as i64 - left as i64;
+        let height = bottom as i64 - top as i64;
+        if width * height > MAX_PIXELS {
+            return Err(HeatmapError::TooLarge { width, height });
+        }
+
+        let mut heatmap = Heatmap {
+            origin: (left, top),
+            width: width as usize,
+            height: height as usize,
+            pixels: vec![UNCLAIMED; (width * height) as usize],
+        };
+
+        let most = fabric.most_claims();
+        for ((left, top, right, bottom), claims) in fabric.cells() {
+            if claims > 0 {
+                heatmap.paint(left..right, top..bottom, heat(claims, most));
+            }
+        }
+        Ok(heatmap)
+    }
+
+    pub fn pixel(&self, x: i32, y: i32) -> Option<Colour> {
+        let (column, row) = (x - self.origin.0, y - self.origin.1);
+        if column < 0 || row < 0 || column as usize >= self.width || row as usize >= self.height {
+            None
+        } else {
+            Some(self.pixels[row as usize * self.width + column as usize])
+        }
+    }
+
+    /// Colours the square inches from the start of each range up to but
+    /// not including its end, leaving out any outside of the image
+    fn paint(&mut self, columns: Range<i32>, rows: Range<i32>, colour: Colour) {
+        let clamp = |range: Range<i32>, origin: i32, size: usize| {
+            let start = (range.start as i64 - origin as i64).clamp(0, size as i64) as usize;
+            let end = (range.end as i64 - origin as i64).clamp(0, size as i64) as usize;
+            start..end.max(start)
+        };
+        let columns = clamp(columns, self.origin.0, self.width);
+
+        for row in clamp(rows, self.origin.1, self.height) {
+            let offset = row * self.width;
+            self.pixels[offset + columns.start..offset + columns.end].fill(colour);
+        }
+    }
+
+    /// Colours every square inch of the claim
+    pub fn fill(&mut self, rect: &Rect, colour: Colour) {
+        self.paint(rect.x..rect.right(), rect.y..rect.bottom(), colour);
+    }
+
+    /// Colours the square inches along the edges of the claim
+    pub fn outline(&mut self, rect: &Rect, colour: Colour) {
+        let (right, bottom) = (rect.right(), rect.bottom());
+
+        self.paint(rect.x..right, rect.y..rect.y + 1, colour);
+        self.paint(rect.x..right, bottom - 1..bottom, colour);
+        self.paint(rect.x..rect.x + 1, rect.y..bottom, colour);
+        self.paint(right - 1..right, rect.y..bottom, colour);
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+
+    /// Writes the image as a binary PPM
+    pub fn write_ppm<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(&self.bytes())?;
+        writer.flush()
+    }
+
+    pub fn write_png<W: Write>(&self, writer: W) -> Result<(), HeatmapError> {
+        let too_large = |_| HeatmapError::TooLarge {
+            width: self.width as i64,
+            height: self.height as i64,
+        };
+        let width = u32::try_from(self.width).map_err(too_large)?;
+        let height = u32::try_from(self.height).map_err(too_large)?;
+
+        let mut encoder = png::Encoder::new(writer, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.bytes())?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Writes the image in the format given by the extension of the path
+    pub fn save(&self, path: &Path) -> Result<(), HeatmapError> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("ppm") => Ok(self.write_ppm(BufWriter::new(File::create(path)?))?),
+            Some("png") => self.write_png(BufWriter::new(File::create(path)?)),
+            _ => Err(HeatmapError::UnknownFormat(path.to_path_buf())),
+        }
+    }
+}
+
+/// Options for the image, taken out of the command line before the
+/// options understood by every day
+#[derive(Debug, Default, PartialEq)]
+pub struct ImageOptions {
+    /// Write the heatmap to this file
+    pub path: Option<PathBuf>,
+    /// Outline the claim with this id
+    pub outline: Option<String>,
+}
+
+impl ImageOptions {
+    /// The image options and the arguments left over
+    pub fn parse<I, S>(args: I) -> Result<(ImageOptions, Vec<String>), OptionsError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut options = ImageOptions::default();
+        let mut rest = vec![];
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            let mut value = || {
+                args.next()
+                    .map(|v| String::from(v.as_ref()))
+                    .ok_or_else(|| OptionsError::MissingValue(String::from(arg)))
+            };
+
+            match arg {
+                "--image" => options.path = Some(PathBuf::from(value()?)),
+                "--outline" => options.outline = Some(value()?),
+                other => rest.push(String::from(other)),
+            }
+        }
+
+        if options.outline.is_some() && options.path.is_none() {
+            return Err(OptionsError::Requires(
+                String::from("--outline"),
+                String::from("--image"),
+            ));
+        }
+        Ok((options, rest))
+    }
+}
+
+#[cfg(test)]
+mod test_heatmap {
+    use super::*;
+
+    fn example() -> (Vec<Rect>, Heatmap) {
+        let rects: Vec<Rect> = ["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 5,5: 2x2"]
+            .iter()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        let heatmap = Heatmap::new(&Fabric::new(&rects)).unwrap();
+        (rects, heatmap)
+    }
+
+    #[test]
+    fn test_heat() {
+        assert_eq!(heat(0, 5), UNCLAIMED);
+        assert_eq!(heat(1, 5), CLAIMED);
+        assert_eq!(heat(2, 5), [255, 224, 32]);
+        assert_eq!(heat(5, 5), [255, 0, 32]);
+        assert_eq!(heat(2, 2), [255, 224, 32]);
+    }
+
+    #[test]
+    fn test_pixels() {
+        let (_, heatmap) = example();
+
+        assert_eq!((heatmap.width, heatmap.height), (6, 6));
+        assert_eq!(heatmap.pixel(1, 1), Some(UNCLAIMED));
+        assert_eq!(heatmap.pixel(1, 3), Some(CLAIMED));
+        assert_eq!(heatmap.pixel(3, 3), Some(heat(2, 2)));
+        assert_eq!(heatmap.pixel(0, 0), None);
+        assert_eq!(heatmap.pixel(7, 1), None);
+    }
+
+    #[test]
+    fn test_matches_claims_at() {
+        let rects: Vec<Rect> = ["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 2,2: 3x5"]
+            .iter()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        let fabric = Fabric::new(&rects);
+        let heatmap = Heatmap::new(&fabric).unwrap();
+
+        for y in 1..7 {
+            for x in 1..7 {
+                let expected = heat(fabric.claims_at(x, y), fabric.most_claims());
+                assert_eq!(heatmap.pixel(x, y), Some(expected), "{},{}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_too_large() {
+        let rects: Vec<Rect> = ["#1 @ 0,0: 2x2", "#2 @ 1000000,1000000: 2x2"]
+            .iter()
+            .map(|line| line.parse().unwrap())
+            .collect();
+
+        assert!(matches!(
+            Heatmap::new(&Fabric::new(&rects)),
+            Err(HeatmapError::TooLarge {
+                width: 1000002,
+                height: 1000002
+            })
+        ));
+    }
+
+    #[test]
+    fn test_fill_and_outline() {
+        let (rects, mut heatmap) = example();
+        heatmap.fill(&rects[2], INTACT);
+        heatmap.outline(&rects[0], OUTLINE);
+
+        assert_eq!(heatmap.pixel(5, 5), Some(INTACT));
+        assert_eq!(heatmap.pixel(6, 6), Some(INTACT));
+        assert_eq!(heatmap.pixel(1, 3), Some(OUTLINE));
+        assert_eq!(heatmap.pixel(4, 6), Some(OUTLINE));
+        assert_eq!(heatmap.pixel(2, 4), Some(CLAIMED));
+    }
+
+    #[test]
+    fn test_ppm() -> io::Result<()> {
+        let (_, heatmap) = example();
+        let mut bytes = vec![];
+        heatmap.write_ppm(&mut bytes)?;
+
+        let header = b"P6\n6 6\n255\n";
+        assert_eq!(&bytes[..header.len()], header);
+        assert_eq!(bytes.len(), header.len() + 6 * 6 * 3);
+        assert_eq!(&bytes[header.len()..header.len() + 3], &UNCLAIMED);
+        Ok(())
+    }
+
+    #[test]
+    fn test_png() -> Result<(), HeatmapError> {
+        let (_, heatmap) = example();
+        let mut bytes = vec![];
+        heatmap.write_png(&mut bytes)?;
+
+        assert_eq!(&bytes[..8], b"\x89PNG\r\n\x1a\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_save() -> Result<(), HeatmapError> {
+        let (_, heatmap) = example();
+        let path = std::env::temp_dir().join(format!("fabric-{}.ppm", std::process::id()));
+
+        heatmap.save(&path)?;
+        let size = std::fs::metadata(&path)?.len();
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(size, 11 + 6 * 6 * 3);
+        assert!(matches!(
+            heatmap.save(Path::new("fabric.bmp")),
+            Err(HeatmapError::UnknownFormat(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_options() {
+        let (options, rest) =
+            ImageOptions::parse(vec!["--explain", "--image", "out.png", "--outline", "#7"])
+                .unwrap();
+
+        assert_eq!(
+            options,
+            ImageOptions {
+                path: Some(PathBuf::from("out.png")),
+                outline: Some(String::from("#7")),
+            }
+        );
+        assert_eq!(rest, vec!["--explain"]);
+        assert_eq!(
+            ImageOptions::parse(vec!["--image"]),
+            Err(OptionsError::MissingValue(String::from("--image")))
+        );
+        assert_eq!(
+            ImageOptions::parse(vec!["--outline", "#7"]),
+            Err(OptionsError::Requires(
+                String::from("--outline"),
+                String::from("--image")
+            ))
+        );
+    }
+}
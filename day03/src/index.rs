@@ -0,0 +1,347 @@
+// Spatial index over the claims, an R-tree bulk loaded with the
+// sort-tile-recursive method: claims are sorted into vertical strips by
+// the middle of their columns, each strip is sorted by rows and cut into
+// leaves, and the leaves are packed the same way until one node is left.
+// A search only descends into nodes whose bounds meet the area searched.
+// https://en.wikipedia.org/wiki/R-tree
+//
+// Areas are half open, so a claim at 1,1 of size 2x2 covers the square
+// inches from 1,1 up to but not including 3,3, and claims which only
+// share an edge do not overlap.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use common::union_find::UnionFind;
+
+use crate::rect::Rect;
+
+/// Most entries held by each node of the tree
+const CAPACITY: usize = 8;
+
+/// Middle of the area, doubled so that it stays whole
+fn centre(area: &Rect) -> (i64, i64) {
+    (
+        area.x as i64 + area.right() as i64,
+        area.y as i64 + area.bottom() as i64,
+    )
+}
+
+#[derive(Debug)]
+enum Node {
+    /// Area covering the claims and their positions
+    Leaf(Rect, Vec<usize>),
+    /// Area covering the nodes and their positions
+    Branch(Rect, Vec<usize>),
+}
+
+impl Node {
+    fn area(&self) -> &Rect {
+        match self {
+            Node::Leaf(area, _) | Node::Branch(area, _) => area,
+        }
+    }
+}
+
+pub struct ClaimIndex<'a> {
+    rects: &'a [Rect],
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+
+/// Groups the entries into nodes of at most CAPACITY entries, each
+/// covering a compact area
+fn pack(mut entries: Vec<(Rect, usize)>) -> Vec<(Rect, Vec<usize>)> {
+    let nodes = entries.len().div_ceil(CAPACITY);
+    let strips = (nodes as f64).sqrt().ceil().max(1.0) as usize;
+    let per_strip = strips * CAPACITY;
+
+    entries.sort_by_key(|(area, _)| centre(area).0);
+
+    let mut packed = vec![];
+    for strip in entries.chunks_mut(per_strip) {
+        strip.sort_by_key(|(area, _)| centre(area).1);
+
+        for group in strip.chunks(CAPACITY) {
+            let area = group
+                .iter()
+                .map(|(area, _)| area.clone())
+                .reduce(|a, b| a.join(&b))
+                .expect("chunks are never empty");
+            packed.push((area, group.iter().map(|(_, entry)| *entry).collect()));
+        }
+    }
+    packed
+}
+
+impl<'a> ClaimIndex<'a> {
+    /// Claims with no area are left out, as they can never overlap
+    pub fn new(rects: &'a [Rect]) -> ClaimIndex<'a> {
+        let claims: Vec<(Rect, usize)> = rects
+            .iter()
+            .cloned()
+            .zip(0..)
+            .filter(|(rect, _)| rect.area() > 0)
+            .collect();
+
+        let mut nodes = vec![];
+        let mut level: Vec<(Rect, usize)> = pack(claims)
+            .into_iter()
+            .map(|(area, entries)| {
+                nodes.push(Node::Leaf(area.clone(), entries));
+                (area, nodes.len() - 1)
+            })
+            .collect();
+
+        while level.len() > 1 {
+            level = pack(level)
+                .into_iter()
+                .map(|(area, entries)| {
+                    nodes.push(Node::Branch(area.clone(), entries));
+                    (area, nodes.len() - 1)
+                })
+                .collect();
+        }
+
+        ClaimIndex {
+            rects,
+            root: level.first().map(|(_, node)| *node),
+            nodes,
+        }
+    }
+
+    /// Positions of the claims which meet the condition, in order. Any
+    /// area containing a claim which meets it must meet it too
+    fn search(&self, meets: impl Fn(&Rect) -> bool) -> Vec<usize> {
+        let mut found = vec![];
+        let mut pending: Vec<usize> = self.root.into_iter().collect();
+
+        while let Some(node) = pending.pop() {
+            match &self.nodes[node] {
+                Node::Leaf(_, claims) => {
+                    found.extend(claims.iter().filter(|&&claim| meets(&self.rects[claim])))
+                }
+                Node::Branch(_, children) => pending.extend(
+                    children
+                        .iter()
+                        .filter(|&&child| meets(self.nodes[child].area())),
+                ),
+            }
+        }
+
+        found.sort_unstable();
+        found
+    }
+
+    /// The claim with the given id
+    pub fn claim(&self, id: u32) -> Option<&'a Rect> {
+        self.rects.iter().find(|rect| rect.id == id)
+    }
+
+    /// Claims covering the square inch at x, y
+    pub fn at(&self, x: i32, y: i32) -> Vec<&'a Rect> {
+        self.search(|area| area.contains(x, y))
+            .into_iter()
+            .map(|i| &self.rects[i])
+            .collect()
+    }
+
+    /// Positions of the other claims overlapping the claim at the position
+    fn neighbours(&self, claim: usize) -> Vec<usize> {
+        let rect = &self.rects[claim];
+        let mut found = self.search(|area| area.overlaps(rect));
+        found.retain(|&other| other != claim);
+        found
+    }
+
+    /// Other claims overlapping the claim with the given id, or None if
+    /// there is no such claim
+    pub fn overlapping(&self, id: u32) -> Option<Vec<&'a Rect>> {
+        let claim = self.claim(id)?;
+        let position = self.rects.iter().position(|r| std::ptr::eq(r, claim))?;
+
+        Some(
+            self.neighbours(position)
+                .into_iter()
+                .map(|i| &self.rects[i])
+                .collect(),
+        )
+    }
+
+    /// Every claim which does not overlap any other, in order
+    pub fn intact(&self) -> Vec<&'a Rect> {
+        (0..self.rects.len())
+            .filter(|&i| self.neighbours(i).is_empty())
+            .map(|i| &self.rects[i])
+            .collect()
+    }
+
+    /// Which claims overlap which
+    pub fn graph(&self) -> OverlapGraph<'a> {
+        let edges = (0..self.rects.len())
+            .flat_map(|i| {
+                self.neighbours(i)
+                    .into_iter()
+                    .filter(move |&j| i < j)
+                    .map(move |j| (i, j))
+            })
+            .collect();
+
+        OverlapGraph {
+            rects: self.rects,
+            edges,
+        }
+    }
+}
+
+/// Claims joined wherever two of them overlap
+pub struct OverlapGraph<'a> {
+    rects: &'a [Rect],
+    /// Positions of each pair of overlapping claims, the earlier first
+    edges: BTreeSet<(usize, usize)>,
+}
+
+impl<'a> OverlapGraph<'a> {
+    pub fn edges(&self) -> impl Iterator<Item = (&'a Rect, &'a Rect)> + '_ {
+        self.edges
+            .iter()
+            .map(move |&(a, b)| (&self.rects[a], &self.rects[b]))
+    }
+
+    /// Groups of claims linked by overlaps, leaving out claims which
+    /// overlap nothing. Claims in each group and the groups themselves
+    /// are in the order the claims were given
+    pub fn components(&self) -> Vec<Vec<&'a Rect>> {
+        let mut groups = UnionFind::new(self.rects.len());
+        for &(a, b) in &self.edges {
+            groups.union(a, b);
+        }
+
+        let mut components: Vec<Vec<&'a Rect>> = vec![];
+        let mut positions = vec![None; self.rects.len()];
+        let linked: BTreeSet<usize> = self.edges.iter().flat_map(|&(a, b)| [a, b]).collect();
+
+        for claim in linked {
+            let position = *positions[groups.find(claim)].get_or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+            components[position].push(&self.rects[claim]);
+        }
+        components
+    }
+
+    /// The graph in the Graphviz dot language
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph overlaps {\n");
+        for (a, b) in self.edges() {
+            writeln!(dot, "  \"#{}\" -- \"#{}\";", a.id, b.id).expect("writing to a string");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod test_claim_index {
+    use super::*;
+
+    fn rects(lines: &[&str]) -> Vec<Rect> {
+        lines.iter().map(|line| line.parse().unwrap()).collect()
+    }
+
+    fn ids(rects: &[&Rect]) -> Vec<u32> {
+        rects.iter().map(|r| r.id).collect()
+    }
+
+    fn example() -> Vec<Rect> {
+        rects(&["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 5,5: 2x2"])
+    }
+
+    #[test]
+    fn test_at() {
+        let rects = example();
+        let index = ClaimIndex::new(&rects);
+
+        assert_eq!(ids(&index.at(3, 3)), vec![1, 2]);
+        assert_eq!(ids(&index.at(1, 3)), vec![1]);
+        assert_eq!(ids(&index.at(6, 6)), vec![3]);
+        assert!(index.at(0, 0).is_empty());
+        assert!(index.at(7, 7).is_empty());
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let rects = example();
+        let index = ClaimIndex::new(&rects);
+
+        assert_eq!(index.overlapping(1).map(|r| ids(&r)), Some(vec![2]));
+        assert_eq!(index.overlapping(2).map(|r| ids(&r)), Some(vec![1]));
+        assert_eq!(index.overlapping(3).map(|r| ids(&r)), Some(vec![]));
+        assert!(index.overlapping(4).is_none());
+    }
+
+    #[test]
+    fn test_intact() {
+        let rects = rects(&[
+            "#1 @ 1,3: 4x4",
+            "#2 @ 3,1: 4x4",
+            "#3 @ 5,5: 2x2",
+            "#4 @ 7,1: 1x1",
+        ]);
+        let index = ClaimIndex::new(&rects);
+
+        assert_eq!(ids(&index.intact()), vec![3, 4]);
+    }
+
+    #[test]
+    fn test_many_claims() {
+        // a grid of claims, each overlapping the next along its row
+        let lines: Vec<String> = (0..20)
+            .flat_map(|y| (0..20).map(move |x| (x, y)))
+            .map(|(x, y)| format!("#{} @ {},{}: 4x2", y * 20 + x + 1, x * 3, y * 10))
+            .collect();
+        let rects: Vec<Rect> = lines.iter().map(|l| l.parse().unwrap()).collect();
+        let index = ClaimIndex::new(&rects);
+
+        for rect in &rects {
+            let expected: Vec<&Rect> = rects
+                .iter()
+                .filter(|other| other.id != rect.id && other.overlaps(rect))
+                .collect();
+            assert_eq!(index.overlapping(rect.id), Some(expected));
+        }
+
+        assert_eq!(index.graph().components().len(), 20);
+        assert_eq!(ids(&index.at(3, 11)), vec![21, 22]);
+    }
+
+    #[test]
+    fn test_components() {
+        let rects = rects(&[
+            "#1 @ 0,0: 2x2",
+            "#2 @ 10,10: 2x2",
+            "#3 @ 1,1: 2x2",
+            "#4 @ 20,20: 1x1",
+            "#5 @ 11,11: 2x2",
+            "#6 @ 2,2: 2x2",
+        ]);
+        let graph = ClaimIndex::new(&rects).graph();
+
+        let components: Vec<Vec<u32>> = graph.components().iter().map(|c| ids(c)).collect();
+        assert_eq!(components, vec![vec![1, 3, 6], vec![2, 5]]);
+        assert_eq!(
+            graph.to_dot(),
+            "graph overlaps {\n  \"#1\" -- \"#3\";\n  \"#2\" -- \"#5\";\n  \"#3\" -- \"#6\";\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let index = ClaimIndex::new(&[]);
+
+        assert!(index.at(0, 0).is_empty());
+        assert!(index.intact().is_empty());
+        assert!(index.graph().components().is_empty());
+    }
+}
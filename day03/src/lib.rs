@@ -0,0 +1,287 @@
+// https://adventofcode.com/2018/day/3
+use common::explain::Explanation;
+use common::prefix::{Difference, SummedArea};
+use index::ClaimIndex;
+use rect::Rect;
+
+pub mod heatmap;
+pub mod index;
+pub mod rect;
+
+/// The fabric covered by every claim. Only the edges of the claims
+/// matter, so the fabric is cut along them into cells which are each
+/// covered by the same claims throughout, rather than into square inches.
+/// Its size then depends on the number of claims and not their size
+pub struct Fabric {
+    /// Position of each edge between the columns of cells, in order
+    columns: Vec<i32>,
+    /// Position of each edge between the rows of cells, in order
+    rows: Vec<i32>,
+    /// Number of claims covering each cell
+    claims: Vec<Vec<i32>>,
+    /// Square inches in cells covered by two or more claims
+    overlaps: SummedArea<i64>,
+}
+
+/// Sorted positions of every edge of the claims along one axis
+fn edges(rects: &[Rect], edge: impl Fn(&Rect) -> (i32, i32)) -> Vec<i32> {
+    let mut edges: Vec<i32> = rects
+        .iter()
+        .map(edge)
+        .filter(|(start, end)| start < end)
+        .flat_map(|(start, end)| vec![start, end])
+        .collect();
+    edges.sort_unstable();
+    edges.dedup();
+    edges
+}
+
+/// Cells between the edges which are covered by the span from start to end
+fn cells(edges: &[i32], start: i32, end: i32) -> (usize, usize) {
+    let count = edges.len().saturating_sub(1);
+    let first = edges.partition_point(|&e| e <= start).saturating_sub(1);
+    let last = edges.partition_point(|&e| e < end).min(count);
+    (first.min(last), last)
+}
+
+impl Fabric {
+    pub fn new(rects: &[Rect]) -> Fabric {
+        let columns = edges(rects, |r| (r.x, r.right()));
+        let rows = edges(rects, |r| (r.y, r.bottom()));
+
+        let width = columns.len().saturating_sub(1);
+        let height = rows.len().saturating_sub(1);
+
+        let mut difference = Difference::new(width, height);
+        for rect in rects.iter().filter(|r| r.area() > 0) {
+            let (left, right) = cells(&columns, rect.x, rect.right());
+            let (top, bottom) = cells(&rows, rect.y, rect.bottom());
+            difference.add(left, top, right - left, bottom - top, 1);
+        }
+
+        let claims = difference.values();
+        let area = |x: usize, y: usize| {
+            (columns[x + 1] - columns[x]) as i64 * (rows[y + 1] - rows[y]) as i64
+        };
+        let overlaps =
+            SummedArea::from_fn(
+                width,
+                height,
+                |x, y| {
+                    if claims[y][x] > 1 {
+                        area(x, y)
+                    } else {
+                        0
+                    }
+                },
+            );
+
+        Fabric {
+            columns,
+            rows,
+            claims,
+            overlaps,
+        }
+    }
+
+    /// Square inches within at least one claim
+    pub fn claimed(&self) -> usize {
+        let mut total = 0;
+        for (y, row) in self.claims.iter().enumerate() {
+            for (x, &count) in row.iter().enumerate() {
+                if count > 0 {
+                    let width = self.columns[x + 1] - self.columns[x];
+                    let height = self.rows[y + 1] - self.rows[y];
+                    total += width as usize * height as usize;
+                }
+            }
+        }
+        total
+    }
+
+    /// Area covered by the claims, as left, top, right and bottom,
+    /// with the right and bottom edges just outside of it
+    pub fn bounds(&self) -> (i32, i32, i32, i32) {
+        match (self.columns.first(), self.columns.last()) {
+            (Some(&left), Some(&right)) => {
+                (left, self.rows[0], right, self.rows[self.rows.len() - 1])
+            }
+            _ => (0, 0, 0, 0),
+        }
+    }
+
+    /// Each cell of the fabric as its left, top, right and bottom edges,
+    /// along with the number of claims covering it
+    pub fn cells(&self) -> impl Iterator<Item = ((i32, i32, i32, i32), i32)> + '_ {
+        self.claims.iter().enumerate().flat_map(move |(y, row)| {
+            row.iter().enumerate().map(move |(x, &count)| {
+                let area = (
+                    self.columns[x],
+                    self.rows[y],
+                    self.columns[x + 1],
+                    self.rows[y + 1],
+                );
+                (area, count)
+            })
+        })
+    }
+
+    /// Number of claims covering the square inch at x, y
+    pub fn claims_at(&self, x: i32, y: i32) -> i32 {
+        let column = self.columns.partition_point(|&e| e <= x);
+        let row = self.rows.partition_point(|&e| e <= y);
+
+        if column == 0 || row == 0 || column == self.columns.len() || row == self.rows.len() {
+            0
+        } else {
+            self.claims[row - 1][column - 1]
+        }
+    }
+
+    /// Most claims covering any one square inch
+    pub fn most_claims(&self) -> i32 {
+        self.claims.iter().flatten().copied().max().unwrap_or(0)
+    }
+
+    /// Square inches within two or more claims
+    pub fn overlapping(&self) -> usize {
+        self.overlaps.total() as usize
+    }
+
+    /// Whether none of the claim overlaps with any other claim
+    pub fn is_intact(&self, rect: &Rect) -> bool {
+        let (left, right) = cells(&self.columns, rect.x, rect.right());
+        let (top, bottom) = cells(&self.rows, rect.y, rect.bottom());
+
+        self.overlaps.sum(left, top, right - left, bottom - top) == 0
+    }
+}
+
+pub fn explain_collisions(rects: &[Rect], fabric: &Fabric, collisions: usize) -> Explanation {
+    let graph = ClaimIndex::new(rects).graph();
+
+    Explanation::new("Part 1: square inches within two or more claims")
+        .fact("claims", rects.len())
+        .fact("pairs of overlapping claims", graph.edges().count())
+        .fact("groups of overlapping claims", graph.components().len())
+        .fact("square inches claimed", fabric.claimed())
+        .fact("square inches claimed more than once", collisions)
+}
+
+pub fn explain_intact_claim(rect: &Rect) -> Explanation {
+    Explanation::new("Part 2: claim which does not overlap")
+        .fact("claim", format!("#{}", rect.id))
+        .fact("position", format!("{},{}", rect.x, rect.y))
+        .fact("size", format!("{}x{}", rect.width, rect.height))
+        .fact("square inches", rect.area())
+}
+
+#[cfg(test)]
+mod test_fabric {
+    use super::*;
+
+    fn rects(lines: &[&str]) -> Vec<Rect> {
+        lines.iter().map(|line| line.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_example() {
+        let rects = rects(&["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 5,5: 2x2"]);
+        let fabric = Fabric::new(&rects);
+
+        assert_eq!(fabric.overlapping(), 4);
+        assert_eq!(fabric.claimed(), 16 + 16 - 4 + 4);
+        assert_eq!(
+            rects
+                .iter()
+                .map(|r| fabric.is_intact(r))
+                .collect::<Vec<_>>(),
+            vec![false, false, true]
+        );
+    }
+
+    #[test]
+    fn test_touching() {
+        // claims which share an edge do not overlap
+        let rects = rects(&["#1 @ 0,0: 2x2", "#2 @ 2,0: 2x2", "#3 @ 0,1: 1x1"]);
+        let fabric = Fabric::new(&rects);
+
+        assert_eq!(fabric.overlapping(), 1);
+        assert!(!fabric.is_intact(&rects[0]));
+        assert!(fabric.is_intact(&rects[1]));
+    }
+
+    #[test]
+    fn test_claims_at() {
+        let rects = rects(&["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 5,5: 2x2"]);
+        let fabric = Fabric::new(&rects);
+
+        assert_eq!(fabric.bounds(), (1, 1, 7, 7));
+        assert_eq!(fabric.claims_at(0, 0), 0);
+        assert_eq!(fabric.claims_at(1, 3), 1);
+        assert_eq!(fabric.claims_at(4, 4), 2);
+        assert_eq!(fabric.claims_at(5, 5), 1);
+        assert_eq!(fabric.claims_at(6, 2), 1);
+        assert_eq!(fabric.claims_at(7, 7), 0);
+        assert_eq!(fabric.most_claims(), 2);
+    }
+
+    #[test]
+    fn test_large_claims() {
+        let rects = rects(&[
+            "#1 @ 0,0: 100000x100000",
+            "#2 @ 99999,99999: 5000x5000",
+            "#3 @ 200000,5: 3000x2",
+            "#4 @ 50,50: 10x10",
+        ]);
+        let fabric = Fabric::new(&rects);
+
+        assert_eq!(fabric.overlapping(), 1 + 100);
+        assert_eq!(fabric.claimed(), 10_000_000_000 + 25_000_000 - 1 + 6000);
+        assert_eq!(
+            rects
+                .iter()
+                .map(|r| fabric.is_intact(r))
+                .collect::<Vec<_>>(),
+            vec![false, false, true, false]
+        );
+    }
+
+    #[test]
+    fn test_matches_square_inches() {
+        let rects = rects(&[
+            "#1 @ 1,3: 4x4",
+            "#2 @ 3,1: 4x4",
+            "#3 @ 5,5: 2x2",
+            "#4 @ 2,2: 1x6",
+            "#5 @ 0,6: 8x1",
+            "#6 @ 9,9: 1x1",
+        ]);
+        let fabric = Fabric::new(&rects);
+
+        let mut counts = [[0; 10]; 10];
+        for r in &rects {
+            for y in r.y..r.bottom() {
+                for x in r.x..r.right() {
+                    counts[y as usize][x as usize] += 1;
+                }
+            }
+        }
+        let squares = |f: fn(i32) -> bool| counts.iter().flatten().filter(|&&c| f(c)).count();
+
+        assert_eq!(fabric.overlapping(), squares(|c| c > 1));
+        assert_eq!(fabric.claimed(), squares(|c| c > 0));
+        for r in &rects {
+            let alone = (r.y..r.bottom())
+                .all(|y| (r.x..r.right()).all(|x| counts[y as usize][x as usize] == 1));
+            assert_eq!(fabric.is_intact(r), alone, "{}", r.id);
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let fabric = Fabric::new(&[]);
+        assert_eq!(fabric.overlapping(), 0);
+        assert_eq!(fabric.claimed(), 0);
+    }
+}
@@ -1,64 +1,79 @@
 // https://adventofcode.com/2018/day/3
-use std::collections::HashMap;
+use common::cli::Options;
+use common::input::{read_stdin, Whitespace};
+use common::report::Report;
+use day3pt1::heatmap::{Heatmap, ImageOptions, INTACT, OUTLINE};
+use day3pt1::index::ClaimIndex;
+use day3pt1::rect::Rect;
+use day3pt1::{explain_collisions, explain_intact_claim, Fabric};
 use std::error::Error;
-use std::io::{self, Read};
 
-mod rect;
+fn main() -> Result<(), Box<dyn Error>> {
+    let (image, args) = ImageOptions::parse(std::env::args().skip(1))?;
+    let options = Options::parse(args)?;
+    let mut report = Report::new(3);
 
-use rect::Rect;
+    let input = read_stdin(Whitespace::Insignificant)?;
 
-type Coord = (i32, i32);
+    let mut rects = vec![];
 
-fn does_not_collide(rect: &Rect, map: &HashMap<Coord, i32>) -> bool {
-    for x in 0..rect.width {
-        for y in 0..rect.height {
-            let key = (rect.x + x, rect.y + y);
-            if let Some(n) = map.get(&key) {
-                if *n > 1 {
-                    return false;
-                }
-            }
-        }
+    for line in input.lines() {
+        let rect: Rect = line.parse()?;
+        rects.push(rect);
     }
-    true
-}
 
-fn add_rect_to_map(rect: &Rect, map: &mut HashMap<Coord, i32>) {
-    for x in 0..rect.width {
-        for y in 0..rect.height {
-            let key = (rect.x + x, rect.y + y);
-            let value = map.entry(key).or_insert(0);
-            *value += 1;
+    // both parts need the fabric, so it is timed as part of whichever part runs first
+    let first = if options.runs(1) { 1 } else { 2 };
+    let fabric = report.time(first, || Fabric::new(&rects));
+
+    if options.runs(1) {
+        let collisions = report.time(1, || fabric.overlapping());
+        report.answer(1, collisions);
+
+        // Result for part 1
+        println!("{}", collisions);
+
+        if options.explain {
+            print!("{}", explain_collisions(&rects, &fabric, collisions));
         }
     }
-}
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
-
-    let mut map: HashMap<Coord, i32> = HashMap::new();
-    let mut rects = vec![];
+    if options.runs(2) {
+        // Result for part 2
+        let intact = report.time(2, || rects.iter().find(|rect| fabric.is_intact(rect)));
 
-    for line in input.lines() {
-        let rect: Rect = line.parse()?;
+        if let Some(rect) = intact {
+            report.answer(2, rect.id);
+            println!("#{} does not collide with any other rect", rect.id);
 
-        add_rect_to_map(&rect, &mut map);
-        rects.push(rect);
+            if options.explain {
+                print!("{}", explain_intact_claim(rect));
+            }
+        }
     }
 
-    let collisions = map.values().filter(|&v| v > &1).count();
-
-    // Result for part 1
-    println!("{}", collisions);
+    if let Some(path) = &image.path {
+        let mut heatmap = Heatmap::new(&fabric)?;
 
-    // Result for part 2
-    for rect in rects {
-        if does_not_collide(&rect, &map) {
-            println!("{} does not collide with any other rect", rect.id);
-            break;
+        if let Some(rect) = rects.iter().find(|rect| fabric.is_intact(rect)) {
+            heatmap.fill(rect, INTACT);
         }
+        if let Some(id) = &image.outline {
+            let number: u32 = id
+                .trim_start_matches('#')
+                .parse()
+                .map_err(|_| format!("Invalid claim id {}", id))?;
+            let claims = ClaimIndex::new(&rects);
+            let rect = claims
+                .claim(number)
+                .ok_or_else(|| format!("There is no claim {}", id))?;
+            heatmap.outline(rect, OUTLINE);
+        }
+
+        heatmap.save(path)?;
     }
 
+    report.write(&options)?;
+
     Ok(())
 }
@@ -1,9 +1,18 @@
-#[derive(PartialEq, Debug)]
+use common::interval::{union, Interval};
+
+/// A claim on the fabric, covering the square inches from x, y up to but
+/// not including x + width, y + height
+#[derive(PartialEq, Debug, Clone)]
 pub struct Rect {
-    pub id: String,
+    /// Number of the claim, written with a leading #
+    pub id: u32,
+    /// Inches from the left edge of the fabric, never negative when parsed
     pub x: i32,
+    /// Inches from the top edge of the fabric, never negative when parsed
     pub y: i32,
+    /// Always greater than zero when parsed
     pub width: i32,
+    /// Always greater than zero when parsed
     pub height: i32,
 }
 
@@ -11,6 +20,18 @@ pub struct Rect {
 pub enum RectError {
     Parse(std::num::ParseIntError),
     Missing(String),
+    InvalidId(String),
+    InvalidSize {
+        width: i32,
+        height: i32,
+    },
+    /// Part of the claim is off the fabric or past the largest i32
+    OutOfRange {
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    },
 }
 
 impl std::fmt::Display for RectError {
@@ -18,6 +39,20 @@ impl std::fmt::Display for RectError {
         match self {
             RectError::Parse(e) => write!(f, "Parse Error: {}", e),
             RectError::Missing(e) => write!(f, "Missing Error: {}", e),
+            RectError::InvalidId(id) => write!(f, "Invalid claim id: {}", id),
+            RectError::InvalidSize { width, height } => {
+                write!(f, "Invalid claim size: {}x{}", width, height)
+            }
+            RectError::OutOfRange {
+                x,
+                y,
+                width,
+                height,
+            } => write!(
+                f,
+                "Claim at {},{} of size {}x{} is out of range",
+                x, y, width, height
+            ),
         }
     }
 }
@@ -42,10 +77,14 @@ impl std::str::FromStr for Rect {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut tokens = s.split("@");
 
-        let id = String::from(tokens.next().ok_or(RectError::is_missing("id"))?.trim());
-
+        let id = tokens.next().ok_or(RectError::is_missing("id"))?.trim();
         let definition = tokens.next().ok_or(RectError::is_missing("definition"))?;
 
+        let id = id
+            .strip_prefix('#')
+            .and_then(|number| number.parse().ok())
+            .ok_or_else(|| RectError::InvalidId(String::from(id)))?;
+
         let mut tokens = definition.split(":");
 
         let mut coordinates = tokens
@@ -78,6 +117,20 @@ impl std::str::FromStr for Rect {
             .ok_or(RectError::is_missing("height"))?
             .parse()?;
 
+        if width <= 0 || height <= 0 {
+            return Err(RectError::InvalidSize { width, height });
+        }
+        // the right and bottom edges must fit, as must the span of any two
+        // claims, which needs every claim to start on the fabric
+        if x < 0 || y < 0 || x.checked_add(width).is_none() || y.checked_add(height).is_none() {
+            return Err(RectError::OutOfRange {
+                x,
+                y,
+                width,
+                height,
+            });
+        }
+
         Ok(Rect {
             id,
             x,
@@ -88,6 +141,116 @@ impl std::str::FromStr for Rect {
     }
 }
 
+impl std::fmt::Display for Rect {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "#{} @ {},{}: {}x{}",
+            self.id, self.x, self.y, self.width, self.height
+        )
+    }
+}
+
+impl Rect {
+    /// Just past the last column of the claim
+    pub fn right(&self) -> i32 {
+        self.x + self.width
+    }
+
+    /// Just past the last row of the claim
+    pub fn bottom(&self) -> i32 {
+        self.y + self.height
+    }
+
+    /// Square inches covered by the claim
+    pub fn area(&self) -> i64 {
+        self.width as i64 * self.height as i64
+    }
+
+    /// Columns covered by the claim
+    pub fn columns(&self) -> Interval<i32> {
+        Interval::new(self.x, self.right())
+    }
+
+    /// Rows covered by the claim
+    pub fn rows(&self) -> Interval<i32> {
+        Interval::new(self.y, self.bottom())
+    }
+
+    /// The part of this claim which the other claim also covers, if any.
+    /// It keeps the id of this claim
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let columns = self.columns().intersection(&other.columns())?;
+        let rows = self.rows().intersection(&other.rows())?;
+
+        Some(Rect {
+            id: self.id,
+            x: columns.start,
+            y: rows.start,
+            width: columns.len(),
+            height: rows.len(),
+        })
+    }
+
+    /// The smallest claim covering both claims. It keeps the id of this claim
+    pub fn join(&self, other: &Rect) -> Rect {
+        let (x, y) = (self.x.min(other.x), self.y.min(other.y));
+
+        Rect {
+            id: self.id,
+            x,
+            y,
+            width: self.right().max(other.right()) - x,
+            height: self.bottom().max(other.bottom()) - y,
+        }
+    }
+
+    /// Whether the claims share any square inch. Claims which only
+    /// touch along an edge do not overlap
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Whether the claim covers the square inch at x, y
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        self.columns().contains(x) && self.rows().contains(y)
+    }
+
+    /// Whether every square inch of the other claim is within this one
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        self.x <= other.x
+            && self.y <= other.y
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+}
+
+/// Square inches covered by at least one of the claims. Sweeps across the
+/// columns, adding up the rows covered between each edge and the next
+pub fn union_area(rects: &[Rect]) -> i64 {
+    let mut edges: Vec<i32> = rects
+        .iter()
+        .filter(|r| r.area() > 0)
+        .flat_map(|r| [r.x, r.right()])
+        .collect();
+    edges.sort_unstable();
+    edges.dedup();
+
+    edges
+        .windows(2)
+        .map(|edge| {
+            let rows: Vec<Interval<i32>> = rects
+                .iter()
+                .filter(|r| r.x <= edge[0] && edge[1] <= r.right())
+                .map(Rect::rows)
+                .collect();
+            let height: i64 = union(&rows).iter().map(|i| i.len() as i64).sum();
+
+            height * (edge[1] - edge[0]) as i64
+        })
+        .sum()
+}
+
 #[cfg(test)]
 mod test_rect {
     use super::*;
@@ -98,7 +261,7 @@ mod test_rect {
         let result: Rect = "#123 @ 3,2: 5x4".parse()?;
 
         let expected = Rect {
-            id: String::from("#123"),
+            id: 123,
             x: 3,
             y: 2,
             width: 5,
@@ -131,4 +294,121 @@ mod test_rect {
         let result = input.parse::<Rect>();
         assert_eq!(result, Err(RectError::is_missing(message)));
     }
+
+    #[rstest(
+        input,
+        expected,
+        case("123 @ 3,2: 5x4", RectError::InvalidId(String::from("123"))),
+        case("#abc @ 3,2: 5x4", RectError::InvalidId(String::from("#abc"))),
+        case("#-1 @ 3,2: 5x4", RectError::InvalidId(String::from("#-1"))),
+        case("#1 @ 3,2: 0x4", RectError::InvalidSize { width: 0, height: 4 }),
+        case("#1 @ 3,2: 5x-4", RectError::InvalidSize { width: 5, height: -4 }),
+        case("#1 @ -3,2: 5x4", RectError::OutOfRange { x: -3, y: 2, width: 5, height: 4 }),
+        case("#1 @ 3,-2: 5x4", RectError::OutOfRange { x: 3, y: -2, width: 5, height: 4 }),
+        case(
+            "#1 @ 2147483000,0: 1000x1",
+            RectError::OutOfRange { x: 2147483000, y: 0, width: 1000, height: 1 }
+        ),
+        case(
+            "#1 @ 0,2147483000: 1x1000",
+            RectError::OutOfRange { x: 0, y: 2147483000, width: 1, height: 1000 }
+        )
+    )]
+    fn test_invalid(input: &str, expected: RectError) {
+        assert_eq!(input.parse::<Rect>(), Err(expected));
+    }
+
+    #[rstest(
+        input,
+        case("#123 @ 3,2: 5x4"),
+        case("#1 @ 0,0: 1x1"),
+        case("#4000000000 @ 5,7: 1000000x2")
+    )]
+    fn test_display_round_trip(input: &str) -> Result<(), RectError> {
+        let rect: Rect = input.parse()?;
+
+        assert_eq!(rect.to_string(), input);
+        assert_eq!(rect.to_string().parse::<Rect>()?, rect);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_geometry {
+    use super::*;
+    use rstest::rstest;
+
+    fn rect(input: &str) -> Rect {
+        input.parse().unwrap()
+    }
+
+    #[rstest(
+        a,
+        b,
+        expected,
+        case("#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", Some("#1 @ 3,3: 2x2")),
+        case("#2 @ 3,1: 4x4", "#1 @ 1,3: 4x4", Some("#2 @ 3,3: 2x2")),
+        case("#1 @ 0,0: 10x10", "#2 @ 2,3: 1x1", Some("#1 @ 2,3: 1x1")),
+        case("#1 @ 1,3: 4x4", "#3 @ 5,5: 2x2", None),
+        case("#1 @ 0,0: 2x2", "#2 @ 5,5: 2x2", None)
+    )]
+    fn test_intersection(a: &str, b: &str, expected: Option<&str>) {
+        let result = rect(a).intersection(&rect(b));
+
+        assert_eq!(result, expected.map(rect));
+        assert_eq!(rect(a).overlaps(&rect(b)), expected.is_some());
+    }
+
+    #[test]
+    fn test_area() {
+        assert_eq!(rect("#1 @ 1,3: 4x4").area(), 16);
+        assert_eq!(rect("#1 @ 0,0: 100000x100000").area(), 10_000_000_000);
+    }
+
+    #[test]
+    fn test_contains() {
+        let a = rect("#1 @ 1,3: 4x4");
+
+        assert!(a.contains(1, 3));
+        assert!(a.contains(4, 6));
+        assert!(!a.contains(5, 6));
+        assert!(!a.contains(0, 3));
+        assert!(a.contains_rect(&rect("#2 @ 2,4: 3x3")));
+        assert!(a.contains_rect(&a));
+        assert!(!a.contains_rect(&rect("#2 @ 2,4: 4x3")));
+    }
+
+    #[test]
+    fn test_join() {
+        let a = rect("#1 @ 1,3: 4x4");
+
+        assert_eq!(a.join(&rect("#2 @ 3,1: 4x4")), rect("#1 @ 1,1: 6x6"));
+        assert_eq!(a.join(&rect("#2 @ 2,4: 1x1")), a);
+    }
+
+    #[test]
+    fn test_last_inch() {
+        let a = rect("#1 @ 2147483000,0: 647x1");
+
+        assert_eq!(a.right(), i32::MAX);
+        assert!(a.contains(i32::MAX - 1, 0));
+        assert!(!a.contains(i32::MAX, 0));
+    }
+
+    #[test]
+    fn test_union_area() {
+        let rects = vec![
+            rect("#1 @ 1,3: 4x4"),
+            rect("#2 @ 3,1: 4x4"),
+            rect("#3 @ 5,5: 2x2"),
+        ];
+
+        assert_eq!(union_area(&rects), 16 + 16 - 4 + 4);
+        assert_eq!(union_area(&rects[..1]), 16);
+        assert_eq!(union_area(&[]), 0);
+        assert_eq!(
+            union_area(&[rect("#1 @ 0,0: 2x2"), rect("#2 @ 0,0: 2x2")]),
+            4
+        );
+    }
 }
@@ -79,7 +79,7 @@ impl std::str::FromStr for Entry {
             .trim();
 
         // note the extra [ in front of the format string
-        let timestamp = NaiveDateTime::parse_from_str(&timestamp, "[%Y-%m-%d %H:%M")?;
+        let timestamp = NaiveDateTime::parse_from_str(timestamp, "[%Y-%m-%d %H:%M")?;
 
         let event = if message == "wakes up" {
             Event::WakesUp
@@ -158,8 +158,8 @@ mod test_entry {
 
         let expected = Entry {
             timestamp: NaiveDateTime::new(
-                NaiveDate::from_ymd(1518, 11, 3),
-                NaiveTime::from_hms(0, 5, 0),
+                NaiveDate::from_ymd_opt(1518, 11, 3).unwrap(),
+                NaiveTime::from_hms_opt(0, 5, 0).unwrap(),
             ),
             event: Event::BeginsShift(Guard(10)),
         };
@@ -175,8 +175,8 @@ mod test_entry {
 
         let expected = Entry {
             timestamp: NaiveDateTime::new(
-                NaiveDate::from_ymd(1518, 11, 1),
-                NaiveTime::from_hms(0, 5, 0),
+                NaiveDate::from_ymd_opt(1518, 11, 1).unwrap(),
+                NaiveTime::from_hms_opt(0, 5, 0).unwrap(),
             ),
             event: Event::FallsAsleep,
         };
@@ -192,8 +192,8 @@ mod test_entry {
 
         let expected = Entry {
             timestamp: NaiveDateTime::new(
-                NaiveDate::from_ymd(1518, 11, 2),
-                NaiveTime::from_hms(0, 50, 0),
+                NaiveDate::from_ymd_opt(1518, 11, 2).unwrap(),
+                NaiveTime::from_hms_opt(0, 50, 0).unwrap(),
             ),
             event: Event::WakesUp,
         };
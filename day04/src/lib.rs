@@ -0,0 +1,248 @@
+use chrono::{NaiveDateTime, Timelike};
+use common::explain::Explanation;
+use common::interval::{Histogram, Interval};
+use entry::{Entry, Event, Guard};
+use std::collections::HashMap;
+
+pub mod entry;
+
+/// Minutes after midnight of the given time
+fn minute_of_day(timestamp: &NaiveDateTime) -> i64 {
+    (timestamp.time().num_seconds_from_midnight() / 60) as i64
+}
+
+/// Every nap taken by each guard, as the minutes after midnight
+/// during which they were asleep
+pub fn find_naps(entries: &[Entry]) -> HashMap<Guard, Vec<Interval<i64>>> {
+    let mut current_guard = None;
+    let mut asleep_at = None;
+    let mut naps: HashMap<Guard, Vec<Interval<i64>>> = HashMap::new();
+
+    for entry in entries {
+        match entry.event {
+            Event::BeginsShift(guard) => {
+                current_guard = Some(guard);
+                asleep_at = None;
+            }
+            Event::FallsAsleep => asleep_at = Some(minute_of_day(&entry.timestamp)),
+            Event::WakesUp => {
+                if let (Some(guard), Some(start)) = (current_guard, asleep_at.take()) {
+                    let end = minute_of_day(&entry.timestamp);
+                    naps.entry(guard)
+                        .or_default()
+                        .push(Interval::new(start, end));
+                }
+            }
+        }
+    }
+
+    naps
+}
+
+fn minutes_asleep(naps: &[Interval<i64>]) -> i64 {
+    naps.iter().map(|nap| nap.len()).sum()
+}
+
+pub fn find_sleepiest_guard(entries: &[Entry]) -> Option<(Guard, Vec<Interval<i64>>)> {
+    find_naps(entries)
+        .into_iter()
+        .max_by_key(|(guard, naps)| (minutes_asleep(naps), guard.0))
+}
+
+/// Find the minute most often spent asleep along with
+/// the number of times it was spent asleep
+pub fn find_highest_freq_minute(naps: &[Interval<i64>]) -> Option<(i64, i64)> {
+    Histogram::of(naps)
+        .busiest()
+        .map(|(minute, freq)| (minute, freq as i64))
+}
+
+pub fn strategy_1(entries: &[Entry]) -> Option<(Guard, i64)> {
+    if let Some((guard, entries)) = find_sleepiest_guard(entries) {
+        if let Some((minute, _)) = find_highest_freq_minute(&entries) {
+            return Some((guard, minute));
+        }
+    }
+    None
+}
+
+pub fn explain_strategy_1(entries: &[Entry]) -> Option<Explanation> {
+    let (guard, naps) = find_sleepiest_guard(entries)?;
+    let (minute, times_asleep) = find_highest_freq_minute(&naps)?;
+
+    Some(
+        Explanation::new("Strategy 1: the guard who spends the most minutes asleep")
+            .fact("guard", format!("#{}", guard.0))
+            .fact("naps", naps.len())
+            .fact("minutes asleep", minutes_asleep(&naps))
+            .fact("sleepiest minute", format!("00:{:02}", minute))
+            .fact("times asleep on that minute", times_asleep)
+            .fact("answer", format!("{} * {}", guard.0, minute)),
+    )
+}
+
+pub fn strategy_2(entries: &[Entry]) -> Option<(Guard, i64)> {
+    find_most_frequent_minute(entries).map(|(guard, minute, _)| (guard, minute))
+}
+
+pub fn explain_strategy_2(entries: &[Entry]) -> Option<Explanation> {
+    let (guard, minute, times_asleep) = find_most_frequent_minute(entries)?;
+
+    Some(
+        Explanation::new("Strategy 2: the guard most frequently asleep on the same minute")
+            .fact("guard", format!("#{}", guard.0))
+            .fact("sleepiest minute", format!("00:{:02}", minute))
+            .fact("times asleep on that minute", times_asleep)
+            .fact("answer", format!("{} * {}", guard.0, minute)),
+    )
+}
+
+/// Find the guard and minute which were spent asleep together the most
+/// along with the number of times the guard was asleep on that minute
+pub fn find_most_frequent_minute(entries: &[Entry]) -> Option<(Guard, i64, i32)> {
+    find_naps(entries)
+        .iter()
+        .filter_map(|(guard, naps)| {
+            let (minute, freq) = find_highest_freq_minute(naps)?;
+            Some((*guard, minute, freq as i32))
+        })
+        .max_by_key(|(guard, _, freq)| (*freq, guard.0))
+}
+
+#[cfg(test)]
+mod test_find_highest_freq_minute {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let result = find_highest_freq_minute(&[]);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_correct_output() {
+        let entries = vec![
+            Interval::new(5, 15),
+            Interval::new(0, 5),
+            Interval::new(6, 10),
+        ];
+
+        let result = find_highest_freq_minute(&entries);
+
+        assert_eq!(result, Some((6, 2)));
+    }
+
+    #[test]
+    fn test_take_first_in_ties() {
+        let entries = vec![
+            Interval::new(0, 10),
+            Interval::new(10, 15),
+            Interval::new(15, 19),
+        ];
+
+        let result = find_highest_freq_minute(&entries);
+
+        assert_eq!(result, Some((0, 1)));
+    }
+}
+
+#[cfg(test)]
+mod test_strategies {
+    use super::*;
+    use entry::EntryError;
+    use std::error::Error;
+
+    fn get_example() -> Result<Vec<Entry>, EntryError> {
+        // NOTE: these entries are already sorted
+        Ok(vec![
+            "[1518-11-01 00:00] Guard #10 begins shift".parse()?,
+            "[1518-11-01 00:05] falls asleep".parse()?,
+            "[1518-11-01 00:25] wakes up".parse()?,
+            "[1518-11-01 00:30] falls asleep".parse()?,
+            "[1518-11-01 00:55] wakes up".parse()?,
+            "[1518-11-01 23:58] Guard #99 begins shift".parse()?,
+            "[1518-11-02 00:40] falls asleep".parse()?,
+            "[1518-11-02 00:50] wakes up".parse()?,
+            "[1518-11-03 00:05] Guard #10 begins shift".parse()?,
+            "[1518-11-03 00:24] falls asleep".parse()?,
+            "[1518-11-03 00:29] wakes up".parse()?,
+            "[1518-11-04 00:02] Guard #99 begins shift".parse()?,
+            "[1518-11-04 00:36] falls asleep".parse()?,
+            "[1518-11-04 00:46] wakes up".parse()?,
+            "[1518-11-05 00:03] Guard #99 begins shift".parse()?,
+            "[1518-11-05 00:45] falls asleep".parse()?,
+            "[1518-11-05 00:55] wakes up".parse()?,
+        ])
+    }
+
+    #[test]
+    fn test_find_naps() -> Result<(), Box<dyn Error>> {
+        let entries = get_example()?;
+
+        let naps = find_naps(&entries);
+
+        assert_eq!(
+            naps[&Guard(10)],
+            vec![
+                Interval::new(5, 25),
+                Interval::new(30, 55),
+                Interval::new(24, 29)
+            ]
+        );
+        assert_eq!(minutes_asleep(&naps[&Guard(99)]), 30);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_strategy_1() -> Result<(), Box<dyn Error>> {
+        let entries = get_example()?;
+
+        let result = strategy_1(&entries);
+        let expected = Some((Guard(10), 24));
+
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_strategy_2() -> Result<(), Box<dyn Error>> {
+        let entries = get_example()?;
+
+        let result = strategy_2(&entries);
+        let expected = Some((Guard(99), 45));
+
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_strategy_1() -> Result<(), Box<dyn Error>> {
+        let entries = get_example()?;
+
+        let explanation = explain_strategy_1(&entries).unwrap();
+
+        assert_eq!(explanation.get("guard"), Some("#10"));
+        assert_eq!(explanation.get("naps"), Some("3"));
+        assert_eq!(explanation.get("minutes asleep"), Some("50"));
+        assert_eq!(explanation.get("sleepiest minute"), Some("00:24"));
+        assert_eq!(explanation.get("times asleep on that minute"), Some("2"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_strategy_2() -> Result<(), Box<dyn Error>> {
+        let entries = get_example()?;
+
+        let explanation = explain_strategy_2(&entries).unwrap();
+
+        assert_eq!(explanation.get("guard"), Some("#99"));
+        assert_eq!(explanation.get("sleepiest minute"), Some("00:45"));
+        assert_eq!(explanation.get("times asleep on that minute"), Some("3"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+pub fn reacts(unit1: char, unit2: char) -> bool {
+    unit1 != unit2 && unit1.eq_ignore_ascii_case(&unit2)
+}
+
+pub fn parse_polymer(polymer: &str, ignore: Option<char>) -> String {
+    let mut result: Vec<char> = vec![];
+
+    for unit in polymer.chars() {
+        if let Some(ignore) = ignore {
+            if ignore.eq_ignore_ascii_case(&unit) {
+                continue;
+            }
+        }
+
+        if let Some(prev_unit) = result.last() {
+            if reacts(*prev_unit, unit) {
+                result.pop();
+                continue;
+            }
+        }
+
+        // if nothing else happens, push the unit
+        result.push(unit);
+    }
+
+    result.iter().collect()
+}
+
+/// Find the unit type whose removal produces the shortest polymer,
+/// along with the polymer produced
+pub fn improve_polymer(polymer: &str) -> (char, String) {
+    let mut polymers = vec![];
+    // There does not seem to a built-in for rust :(
+    for unit in "abcdefghijklmnopqrstuvwxyz".chars() {
+        let result = parse_polymer(polymer, Some(unit));
+        polymers.push((unit, result));
+    }
+
+    polymers
+        .into_iter()
+        .min_by_key(|(_, p)| p.chars().count())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test_reacts {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(
+        unit1,
+        unit2,
+        expected,
+        case('c', 'C', true),
+        case('U', 'u', true),
+        case('A', 'A', false),
+        case('d', 'd', false),
+        case('a', 'b', false),
+        case('F', 'X', false),
+        case('G', 'b', false)
+    )]
+    fn test_reactions(unit1: char, unit2: char, expected: bool) {
+        let result = reacts(unit1, unit2);
+        println!("{} + {} => {}", unit1, unit2, result);
+        assert_eq!(result, expected);
+    }
+}
+
+#[cfg(test)]
+mod test_parse_polymer {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(
+        polymer,
+        output,
+        case("aA", ""),
+        case("abBA", ""),
+        case("abAB", "abAB"),
+        case("aabAAB", "aabAAB"),
+        case("dabAcCaCBAcCcaDA", "dabCBAcaDA")
+    )]
+    fn test_examples_no_ignore(polymer: &str, output: &str) {
+        let result = parse_polymer(polymer, None);
+
+        assert_eq!(result, output);
+    }
+
+    #[rstest(
+        polymer,
+        ignore,
+        output,
+        case("dabAcCaCBAcCcaDA", 'a', "dbCBcD"),
+        case("dabAcCaCBAcCcaDA", 'b', "daCAcaDA"),
+        case("dabAcCaCBAcCcaDA", 'c', "daDA"),
+        case("dabAcCaCBAcCcaDA", 'd', "abCBAc")
+    )]
+    fn test_examples_ignore(polymer: &str, ignore: char, output: &str) {
+        let result = parse_polymer(polymer, Some(ignore));
+
+        assert_eq!(result, output);
+    }
+}
+
+#[cfg(test)]
+mod test_improve_polymer {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let (_, result) = improve_polymer("");
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn test_example() {
+        let result = improve_polymer("dabAcCaCBAcCcaDA");
+        assert_eq!(result, ('c', String::from("daDA")));
+    }
+}
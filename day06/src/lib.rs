@@ -0,0 +1,218 @@
+use common::explain::Explanation;
+use coord::Coord;
+use std::cmp::max;
+use std::collections::HashMap;
+
+pub mod coord;
+
+// get the bottom right corners of the map
+pub fn get_bottom_right(coordinates: &[Coord]) -> Coord {
+    let mut result = Coord { x: 0, y: 0 };
+
+    for coord in coordinates {
+        result.x = max(coord.x, result.x);
+        result.y = max(coord.y, result.y);
+    }
+    result
+}
+
+pub type CoordMap = HashMap<Coord, Option<i32>>;
+
+pub fn get_map(coordinates: &[Coord], bottom_right: &Coord) -> CoordMap {
+    // coordinate -> closest point (by index)
+    let mut coord_map = HashMap::new();
+
+    for x in 0..=bottom_right.x {
+        for y in 0..=bottom_right.y {
+            let current = Coord { x, y };
+            let mut min = i32::MAX;
+            let mut closest = None;
+
+            for (index, coord) in coordinates.iter().enumerate() {
+                let distance = coord.distance_from(&current);
+                if distance < min {
+                    min = distance;
+                    closest = Some(index as i32);
+                } else if distance == min {
+                    closest = None;
+                }
+            }
+            coord_map.insert(current, closest);
+        }
+    }
+
+    coord_map
+}
+
+pub fn touches_edge(coord: &Coord, bottom_right: &Coord) -> bool {
+    coord.x == 0 || coord.y == 0 || coord.x == bottom_right.x || coord.y == bottom_right.y
+}
+
+// get the largest area on the map that is *not* infinite
+// infinite => area touches a border on the map
+pub fn get_largest_area(coord_map: &CoordMap, bottom_right: &Coord) -> Option<i32> {
+    get_finite_areas(coord_map, bottom_right)
+        .into_values()
+        .max()
+}
+
+// get the area closest to each coordinate (by index), leaving out
+// the areas which are infinite
+pub fn get_finite_areas(coord_map: &CoordMap, bottom_right: &Coord) -> HashMap<i32, i32> {
+    let mut area: HashMap<i32, i32> = HashMap::new();
+    let mut edges = vec![];
+
+    for (coord, index) in coord_map.iter() {
+        if let Some(index) = index {
+            let count = area.entry(*index).or_insert(0);
+            *count += 1;
+
+            if touches_edge(coord, bottom_right) {
+                edges.push(index);
+            }
+        }
+    }
+
+    area.into_iter()
+        .filter(|(k, _)| !edges.contains(&k))
+        .collect()
+}
+
+pub fn solution1(coordinates: &[Coord], bottom_right: &Coord) -> Option<i32> {
+    let coord_map = get_map(coordinates, bottom_right);
+    get_largest_area(&coord_map, bottom_right)
+}
+
+pub fn explain_solution1(coordinates: &[Coord], bottom_right: &Coord) -> Option<Explanation> {
+    let coord_map = get_map(coordinates, bottom_right);
+    let areas = get_finite_areas(&coord_map, bottom_right);
+
+    // ties are broken by picking the coordinate which was listed first
+    let (index, area) = areas
+        .iter()
+        .max_by_key(|(index, area)| (**area, -**index))?;
+    let seed = coordinates[*index as usize];
+
+    Some(
+        Explanation::new("Part 1: largest area that is not infinite")
+            .fact("coordinate", format!("{}, {}", seed.x, seed.y))
+            .fact("area", area)
+            .fact("infinite areas", coordinates.len() - areas.len())
+            .fact(
+                "map size",
+                format!("{}x{}", bottom_right.x + 1, bottom_right.y + 1),
+            ),
+    )
+}
+
+pub fn solution2(coordinates: &[Coord], bottom_right: &Coord, max_distance: i32) -> i32 {
+    let mut area = 0;
+    for x in 0..=bottom_right.x {
+        for y in 0..=bottom_right.y {
+            let point = Coord { x, y };
+            let mut total_distance = 0;
+            for coord in coordinates {
+                total_distance += coord.distance_from(&point);
+            }
+
+            if total_distance < max_distance {
+                area += 1;
+            }
+        }
+    }
+
+    area
+}
+
+pub fn explain_solution2(coordinates: &[Coord], max_distance: i32, area: i32) -> Explanation {
+    Explanation::new("Part 2: region close to all coordinates")
+        .fact("coordinates", coordinates.len())
+        .fact("max total distance", max_distance)
+        .fact("area", area)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use coord::CoordError;
+
+    fn get_example() -> Result<Vec<Coord>, CoordError> {
+        Ok(vec![
+            "1, 1".parse()?,
+            "1, 6".parse()?,
+            "8, 3".parse()?,
+            "3, 4".parse()?,
+            "5, 5".parse()?,
+            "8, 9".parse()?,
+        ])
+    }
+
+    #[test]
+    fn test_solution1() -> Result<(), CoordError> {
+        let coordinates = get_example()?;
+
+        // Hard coded from the provided example
+        let bottom_right = Coord { x: 9, y: 9 };
+        let result = solution1(&coordinates, &bottom_right);
+
+        assert_eq!(result, Some(17));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_solution1() -> Result<(), CoordError> {
+        let coordinates = get_example()?;
+
+        let bottom_right = Coord { x: 9, y: 9 };
+        let explanation = explain_solution1(&coordinates, &bottom_right).unwrap();
+
+        assert_eq!(explanation.get("coordinate"), Some("5, 5"));
+        assert_eq!(explanation.get("area"), Some("17"));
+        assert_eq!(explanation.get("infinite areas"), Some("4"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_solution2() -> Result<(), CoordError> {
+        let coordinates = get_example()?;
+
+        let bottom_right = Coord { x: 9, y: 9 };
+        let result = solution2(&coordinates, &bottom_right, 32);
+
+        assert_eq!(result, 16);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_map() -> Result<(), CoordError> {
+        let coordinates = get_example()?;
+
+        // Hard coded from the provided example
+        let bottom_right = Coord { x: 9, y: 9 };
+
+        let map = get_map(&coordinates, &bottom_right);
+
+        // its cumbersome and hard to read comparing a map
+        // result with 81 entries.
+        // Instead we assert by sampling some points
+
+        assert_eq!(map[&"0,0".parse()?], Some(0));
+        assert_eq!(map[&"6,0".parse()?], Some(2));
+        assert_eq!(map[&"0,1".parse()?], Some(0));
+        assert_eq!(map[&"2,4".parse()?], Some(3));
+        assert_eq!(map[&"5,4".parse()?], Some(4));
+        assert_eq!(map[&"0,9".parse()?], Some(1));
+        assert_eq!(map[&"9,9".parse()?], Some(5));
+
+        assert_eq!(map[&"5,0".parse()?], None);
+        assert_eq!(map[&"5,1".parse()?], None);
+        assert_eq!(map[&"0,4".parse()?], None);
+        assert_eq!(map[&"1,4".parse()?], None);
+        assert_eq!(map[&"9,6".parse()?], None);
+        assert_eq!(map[&"8,6".parse()?], None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,6 @@
+// Runs the solution against every case in tests/cases/day06
+
+#[test]
+fn test_cases() {
+    common::cases::assert_cases(env!("CARGO_MANIFEST_DIR"), env!("CARGO_BIN_EXE_day6"));
+}
@@ -0,0 +1,156 @@
+use common::explain::Explanation;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Step(pub char);
+
+pub type Dag = HashMap<Step, Vec<Step>>;
+
+pub fn process_dag(contents: &str) -> Dag {
+    let mut result: HashMap<Step, Vec<Step>> = HashMap::new();
+
+    for line in contents.lines() {
+        let mut tokens = line.split(" must be finished before step ");
+        if let Some(parent) = tokens.next() {
+            let parent = Step(parent.chars().last().unwrap());
+            if let Some(child) = tokens.next() {
+                let child = Step(child.chars().next().unwrap());
+
+                let values = result.entry(child).or_insert(vec![]);
+                values.push(parent);
+
+                result.entry(parent).or_insert(vec![]);
+            }
+        }
+    }
+
+    result
+}
+
+/// All the steps which are not done yet but whose dependencies are,
+/// in alphabetical order
+pub fn find_next_steps(graph: &Dag, done: &HashSet<Step>) -> Vec<Step> {
+    let mut next = vec![];
+
+    for (step, dependencies) in graph {
+        if !done.contains(step) && dependencies.iter().all(|d| done.contains(d)) {
+            next.push(*step);
+        }
+    }
+
+    next.sort_unstable();
+    next
+}
+
+pub fn topological_sort(graph: &Dag) -> Vec<Step> {
+    ordering_decisions(graph)
+        .into_iter()
+        .map(|(step, _)| step)
+        .collect()
+}
+
+/// Order in which steps are completed, along with the steps
+/// that were available to choose from at the time
+pub fn ordering_decisions(graph: &Dag) -> Vec<(Step, Vec<Step>)> {
+    let mut result = vec![];
+    let mut done: HashSet<Step> = HashSet::new();
+
+    loop {
+        let available = find_next_steps(graph, &done);
+
+        // If more than one step is ready, choose the step which is first alphabetically
+        match available.first() {
+            Some(&step) => {
+                done.insert(step);
+                result.push((step, available));
+            }
+            None => break,
+        };
+    }
+
+    result
+}
+
+pub fn explain_topological_sort(graph: &Dag) -> Explanation {
+    let mut explanation = Explanation::new("Part 1: order of the steps").fact("steps", graph.len());
+
+    for (step, available) in ordering_decisions(graph) {
+        explanation = if available.len() == 1 {
+            explanation.step(format!("{} is the only step available", step.0))
+        } else {
+            let available: Vec<String> = available.iter().map(|s| s.0.to_string()).collect();
+            explanation.step(format!(
+                "{} is first alphabetically out of {}",
+                step.0,
+                available.join(", ")
+            ))
+        };
+    }
+
+    explanation
+}
+
+#[cfg(test)]
+mod test_process_dag {
+    use super::*;
+
+    #[test]
+    fn test_provided_example() {
+        let contents = [
+            "Step C must be finished before step A can begin.",
+            "Step C must be finished before step F can begin.",
+            "Step A must be finished before step B can begin.",
+            "Step A must be finished before step D can begin.",
+            "Step B must be finished before step E can begin.",
+            "Step D must be finished before step E can begin.",
+            "Step F must be finished before step E can begin.",
+        ]
+        .join("\n");
+
+        let result = process_dag(&contents);
+
+        let mut graph = HashMap::new();
+        graph.insert(Step('C'), vec![]);
+        graph.insert(Step('A'), vec![Step('C')]);
+        graph.insert(Step('F'), vec![Step('C')]);
+        graph.insert(Step('B'), vec![Step('A')]);
+        graph.insert(Step('D'), vec![Step('A')]);
+        graph.insert(Step('E'), vec![Step('B'), Step('D'), Step('F')]);
+
+        assert_eq!(result, graph);
+
+        let result = topological_sort(&result);
+        let result: String = result.iter().map(|s| s.0).collect();
+
+        let expected = "CABDFE";
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_explain_topological_sort() {
+        let contents = [
+            "Step C must be finished before step A can begin.",
+            "Step C must be finished before step F can begin.",
+            "Step A must be finished before step B can begin.",
+            "Step A must be finished before step D can begin.",
+            "Step B must be finished before step E can begin.",
+            "Step D must be finished before step E can begin.",
+            "Step F must be finished before step E can begin.",
+        ]
+        .join("\n");
+
+        let explanation = explain_topological_sort(&process_dag(&contents));
+
+        let expected = vec![
+            "C is the only step available",
+            "A is first alphabetically out of A, F",
+            "B is first alphabetically out of B, D, F",
+            "D is first alphabetically out of D, F",
+            "F is the only step available",
+            "E is the only step available",
+        ];
+
+        assert_eq!(explanation.steps, expected);
+    }
+}
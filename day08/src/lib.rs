@@ -0,0 +1,62 @@
+use common::explain::Explanation;
+use node::Node;
+
+/// Solution for adventofcode day8
+/// https://adventofcode.com/2018/day/8
+pub mod node;
+
+pub fn explain_metadata_sum(root: &Node) -> Explanation {
+    Explanation::new("Solution 1: sum of every metadata entry")
+        .fact("nodes", root.count())
+        .fact("metadata entries", root.metadata_count())
+        .fact("sum", root.metadata_sum())
+}
+
+pub fn explain_value(root: &Node) -> Explanation {
+    let mut explanation = Explanation::new("Solution 2: value of the root node")
+        .fact("root children", root.children.len())
+        .fact("root metadata", format!("{:?}", root.metadata));
+
+    for &entry in &root.metadata {
+        let step = match (entry as usize)
+            .checked_sub(1)
+            .and_then(|i| root.children.get(i))
+        {
+            Some(child) => format!(
+                "entry {} refers to node {} with value {}",
+                entry,
+                child.id,
+                child.value()
+            ),
+            None => format!("entry {} does not refer to a child and is skipped", entry),
+        };
+        explanation = explanation.step(step);
+    }
+
+    explanation.fact("value", root.value())
+}
+
+#[cfg(test)]
+mod test_explain {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_explain_value() -> Result<(), Box<dyn Error>> {
+        let root: Node = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2".parse()?;
+
+        let explanation = explain_value(&root);
+
+        assert_eq!(explanation.get("value"), Some("66"));
+        assert_eq!(
+            explanation.steps,
+            vec![
+                "entry 1 refers to node 1 with value 33",
+                "entry 1 refers to node 1 with value 33",
+                "entry 2 refers to node 2 with value 0",
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -1,20 +1,39 @@
-/// Solution for adventofcode day8
-/// https://adventofcode.com/2018/day/8
-mod node;
-
-use node::Node;
+use common::cli::Options;
+use common::input::{read_stdin, Whitespace};
+use common::report::Report;
+use day8::node::Node;
+use day8::{explain_metadata_sum, explain_value};
 use std::error::Error;
-use std::io::Read;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let mut contents = String::new();
+    let options = Options::from_args()?;
+    let mut report = Report::new(8);
 
-    std::io::stdin().read_to_string(&mut contents)?;
+    let contents = read_stdin(Whitespace::Insignificant)?;
 
     let result: Node = contents.parse()?;
 
-    println!("Solution 1: {}", result.metadata_sum());
-    println!("Solution 2: {}", result.value());
+    if options.runs(1) {
+        let sum = report.time(1, || result.metadata_sum());
+        report.answer(1, sum);
+        println!("Solution 1: {}", sum);
+
+        if options.explain {
+            print!("{}", explain_metadata_sum(&result));
+        }
+    }
+
+    if options.runs(2) {
+        let value = report.time(2, || result.value());
+        report.answer(2, value);
+        println!("Solution 2: {}", value);
+
+        if options.explain {
+            print!("{}", explain_value(&result));
+        }
+    }
+
+    report.write(&options)?;
 
     Ok(())
 }
@@ -12,7 +12,7 @@ impl FromStr for Node {
     type Err = NodeErr;
     fn from_str(content: &str) -> Result<Self, Self::Err> {
         let mut tokens = content.split_whitespace();
-        Ok(generate_node(&mut tokens, &mut 0)?)
+        generate_node(&mut tokens, &mut 0)
     }
 }
 
@@ -53,6 +53,21 @@ fn generate_node(tokens: &mut SplitWhitespace, current_id: &mut i32) -> Result<N
 }
 
 impl Node {
+    /// Number of nodes in the tree, including this one
+    pub fn count(&self) -> usize {
+        1 + self.children.iter().map(|c| c.count()).sum::<usize>()
+    }
+
+    /// Number of metadata entries in the tree
+    pub fn metadata_count(&self) -> usize {
+        self.metadata.len()
+            + self
+                .children
+                .iter()
+                .map(|c| c.metadata_count())
+                .sum::<usize>()
+    }
+
     // solution for pt1
     pub fn metadata_sum(&self) -> i32 {
         let mut accumulator = 0;
@@ -142,6 +157,8 @@ mod test_node {
 
         assert_eq!(result, expected);
 
+        assert_eq!(result.count(), 4);
+        assert_eq!(result.metadata_count(), 8);
         assert_eq!(result.metadata_sum(), 138);
         assert_eq!(result.value(), 66);
 
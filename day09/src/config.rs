@@ -48,7 +48,7 @@ impl std::str::FromStr for Config {
         let max_points: u32 = max_points
             .replace(" last marble is worth ", "")
             .replace(" points", "")
-            .replace("\n", "")
+            .trim()
             .parse()?;
 
         Ok(Config {
@@ -75,4 +75,14 @@ mod test_config {
 
         Ok(())
     }
+
+    #[test]
+    fn test_crlf() -> Result<(), ConfigErr> {
+        let config: Config = "10 players; last marble is worth 1618 points\r\n".parse()?;
+
+        assert_eq!(config.players, 10);
+        assert_eq!(config.max_points, 1618);
+
+        Ok(())
+    }
 }
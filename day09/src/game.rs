@@ -7,7 +7,6 @@ pub struct Game {
     config: Config,
     board: VecDeque<u32>,
     scores: HashMap<u32, u32>,
-    current_index: usize,
     current_player: u32,
     current_marble: u32,
 }
@@ -30,7 +29,6 @@ impl Game {
             config,
             board,
             scores: HashMap::new(),
-            current_index: 0,
             current_player: 0,
             current_marble: 0,
         }
@@ -40,6 +38,19 @@ impl Game {
         self.scores.values().copied().max()
     }
 
+    /// Player with the highest score along with their score.
+    /// Players are numbered from 1 as in the puzzle description
+    pub fn winner(&self) -> Option<(u32, u32)> {
+        self.scores
+            .iter()
+            .max_by_key(|(&player, &score)| (score, std::cmp::Reverse(player)))
+            .map(|(player, score)| (player + 1, *score))
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
     /// play a round of the game.
     /// The Current marble of the board is always in the front
     /// rotating clockwise is just a matter of traversing from the current marble
@@ -47,7 +58,7 @@ impl Game {
         self.current_marble += 1;
         let marble = self.current_marble;
 
-        if marble % 23 == 0 {
+        if marble.is_multiple_of(23) {
             // move the current index of the board backwards
             for _ in 0..7 {
                 cycle_anticlockwise(&mut self.board);
@@ -104,6 +115,20 @@ mod test_game {
         assert_eq!(game.board, VecDeque::from(vec![4, 2, 1, 3, 0]));
     }
 
+    #[test]
+    fn test_winner() {
+        let config = Config {
+            players: 9,
+            max_points: 25,
+        };
+
+        let mut game = Game::new(config);
+        assert_eq!(game.winner(), None);
+
+        game.play_all();
+        assert_eq!(game.winner(), Some((5, 32)));
+    }
+
     #[test]
     fn test_cycle() {
         let mut board = VecDeque::from(vec![5, 6, 7, 8]);
@@ -148,5 +173,6 @@ mod test_game {
 
         println!("{:?}", game);
         assert_eq!(game.highest_score(), Some(points));
+        assert_eq!(game.winner().map(|(_, score)| score), Some(points));
     }
 }
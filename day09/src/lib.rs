@@ -0,0 +1,19 @@
+// https://adventofcode.com/2018/day/9
+
+use common::explain::Explanation;
+use game::Game;
+
+pub mod config;
+pub mod game;
+
+pub fn explain_game(title: &str, game: &Game) -> Option<Explanation> {
+    let (player, score) = game.winner()?;
+
+    Some(
+        Explanation::new(title)
+            .fact("players", game.config().players)
+            .fact("last marble", game.config().max_points)
+            .fact("winning player", player)
+            .fact("score", score),
+    )
+}
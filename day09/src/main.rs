@@ -1,31 +1,57 @@
 // https://adventofcode.com/2018/day/9
 
-mod config;
-mod game;
-
-use config::Config;
-use game::Game;
-
+use common::cli::Options;
+use common::input::{read_stdin, Whitespace};
+use common::report::Report;
+use day9::config::Config;
+use day9::explain_game;
+use day9::game::Game;
 use std::error::Error;
-use std::io::Read;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let mut contents = String::new();
-
-    std::io::stdin().read_to_string(&mut contents)?;
-
-    let config: Config = contents.parse()?;
-
-    let mut game = Game::new(config);
-    game.play_all();
-    println!("{:?}", game.highest_score());
-
-    let mut config: Config = contents.parse()?;
-    config.max_points *= 100;
-
-    let mut game = Game::new(config);
-    game.play_all();
-    println!("{:?}", game.highest_score());
+    let options = Options::from_args()?;
+    let mut report = Report::new(9);
+
+    let contents = read_stdin(Whitespace::Insignificant)?;
+
+    if options.runs(1) {
+        let config: Config = contents.parse()?;
+
+        let mut game = Game::new(config);
+        report.time(1, || game.play_all());
+        if let Some(score) = game.highest_score() {
+            report.answer(1, score);
+        }
+        println!("{:?}", game.highest_score());
+
+        if options.explain {
+            if let Some(explanation) = explain_game("Part 1: winning score", &game) {
+                print!("{}", explanation);
+            }
+        }
+    }
+
+    if options.runs(2) {
+        let mut config: Config = contents.parse()?;
+        config.max_points *= 100;
+
+        let mut game = Game::new(config);
+        report.time(2, || game.play_all());
+        if let Some(score) = game.highest_score() {
+            report.answer(2, score);
+        }
+        println!("{:?}", game.highest_score());
+
+        if options.explain {
+            if let Some(explanation) =
+                explain_game("Part 2: winning score with 100x marbles", &game)
+            {
+                print!("{}", explanation);
+            }
+        }
+    }
+
+    report.write(&options)?;
 
     Ok(())
 }
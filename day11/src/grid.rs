@@ -1,6 +1,8 @@
+use common::prefix::SummedArea;
+
 pub struct Grid {
     pub data: Vec<Vec<i32>>,
-    pub summed_area: Vec<Vec<i32>>,
+    pub summed_area: SummedArea<i32>,
     pub width: usize,
     pub height: usize,
 }
@@ -19,34 +21,25 @@ fn hundreth(value: i32) -> i32 {
     }
 }
 
+fn power_level(x: usize, y: usize, serial_number: i32) -> i32 {
+    let rack_id = (x + 10) as i32;
+    let mut power_level = rack_id * y as i32;
+    power_level += serial_number;
+    power_level *= rack_id;
+    hundreth(power_level) - 5
+}
+
 impl Grid {
     pub fn generate(width: usize, height: usize, serial_number: i32) -> Grid {
-        let mut data = vec![vec![0; width]; height];
-        let mut summed_area = vec![vec![0; width]; height];
-
-        for x in 0..width {
-            for y in 0..height {
-                let rack_id = (x + 10) as i32;
-                let mut power_level = rack_id * y as i32;
-                power_level += serial_number;
-                power_level *= rack_id;
-                power_level = hundreth(power_level) - 5;
-
-                data[y][x] = power_level;
+        let data: Vec<Vec<i32>> = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| power_level(x, y, serial_number))
+                    .collect()
+            })
+            .collect();
 
-                summed_area[y][x] += data[y][x];
-
-                if y > 0 {
-                    summed_area[y][x] += summed_area[y - 1][x];
-                }
-                if x > 0 {
-                    summed_area[y][x] += summed_area[y][x - 1];
-                }
-                if x > 0 && y > 0 {
-                    summed_area[y][x] -= summed_area[y - 1][x - 1];
-                }
-            }
-        }
+        let summed_area = SummedArea::new(&data);
 
         Grid {
             data,
@@ -57,21 +50,7 @@ impl Grid {
     }
 
     fn calculate_power(&self, point: &Point, size: usize) -> i32 {
-        // Calculates power using the summed area data structure
-        // https://en.wikipedia.org/wiki/Summed-area_table
-        let mut power = 0;
-
-        power += self.summed_area[point.y + size - 1][point.x + size - 1];
-        if point.x > 0 && point.y > 0 {
-            power += self.summed_area[point.y - 1][point.x - 1];
-        }
-        if point.y > 0 {
-            power -= self.summed_area[point.y - 1][point.x + size - 1];
-        }
-        if point.x > 0 {
-            power -= self.summed_area[point.y + size - 1][point.x - 1];
-        }
-        power
+        self.summed_area.sum(point.x, point.y, size, size)
     }
 
     pub fn find_max_power_point(&self) -> (Option<Point>, i32) {
@@ -147,14 +126,18 @@ mod test_grid {
 
         assert_eq!(grid.data, expected_data);
 
-        let expected_summed_area = vec![
+        let expected_summed_area = [
             vec![-5, -10, -15, -20],
             vec![-9, -18, -27, -36],
             vec![-12, -24, -35, -46],
             vec![-14, -28, -40, -51],
         ];
 
-        assert_eq!(grid.summed_area, expected_summed_area);
+        for (y, row) in expected_summed_area.iter().enumerate() {
+            for (x, expected) in row.iter().enumerate() {
+                assert_eq!(grid.summed_area.up_to(x, y), *expected);
+            }
+        }
 
         assert_eq!(grid.calculate_power(&Point { x: 0, y: 0 }, 1), -5);
         assert_eq!(grid.calculate_power(&Point { x: 1, y: 1 }, 1), -4);
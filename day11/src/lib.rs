@@ -0,0 +1,28 @@
+// https://adventofcode.com/2018/day/11
+
+use common::explain::Explanation;
+use grid::{Grid, Point};
+
+pub mod grid;
+
+pub fn explain_square(
+    title: &str,
+    grid: &Grid,
+    point: &Point,
+    size: usize,
+    power: i32,
+) -> Explanation {
+    let cells: Vec<i32> = grid.data[point.y..point.y + size]
+        .iter()
+        .flat_map(|row| row[point.x..point.x + size].iter().copied())
+        .collect();
+
+    Explanation::new(title)
+        .fact("top left", format!("{},{}", point.x, point.y))
+        .fact("size", format!("{}x{}", size, size))
+        .fact(
+            "cells with positive power",
+            cells.iter().filter(|&&c| c > 0).count(),
+        )
+        .fact("total power", power)
+}
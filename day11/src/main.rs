@@ -1,18 +1,61 @@
 // https://adventofcode.com/2018/day/11
 
-mod grid;
+use common::cli::Options;
+use common::report::Report;
+use day11::explain_square;
+use day11::grid::Grid;
+use std::error::Error;
 
-use grid::Grid;
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = Options::from_args()?;
+    let mut report = Report::new(11);
 
-fn main() {
     let serial_number = 7857;
 
-    let grid = Grid::generate(300, 300, serial_number);
-    let (point, power) = grid.find_max_power_point();
+    // both parts need the grid, so it is timed as part of whichever part runs first
+    let first = if options.runs(1) { 1 } else { 2 };
+    let grid = report.time(first, || Grid::generate(300, 300, serial_number));
 
-    println!("{:?} (power: {})", point, power);
+    if options.runs(1) {
+        let (point, power) = report.time(1, || grid.find_max_power_point());
+        if let Some(point) = &point {
+            report.answer(1, format!("{},{}", point.x, point.y));
+        }
 
-    let (point, power) = grid.find_max_power_point_adjustable();
+        println!("{:?} (power: {})", point, power);
 
-    println!("{:?} (power: {})", point, power);
+        if options.explain {
+            if let Some(point) = &point {
+                let explanation =
+                    explain_square("Part 1: most powerful 3x3 square", &grid, point, 3, power);
+                print!("{}", explanation);
+            }
+        }
+    }
+
+    if options.runs(2) {
+        let (point, power) = report.time(2, || grid.find_max_power_point_adjustable());
+        if let Some((point, size)) = &point {
+            report.answer(2, format!("{},{},{}", point.x, point.y, size));
+        }
+
+        println!("{:?} (power: {})", point, power);
+
+        if options.explain {
+            if let Some((point, size)) = &point {
+                let explanation = explain_square(
+                    "Part 2: most powerful square of any size",
+                    &grid,
+                    point,
+                    *size,
+                    power,
+                );
+                print!("{}", explanation);
+            }
+        }
+    }
+
+    report.write(&options)?;
+
+    Ok(())
 }
@@ -0,0 +1,359 @@
+// https://adventofcode.com/2018/day/12
+
+use common::cycle::{Cycle, Detector};
+use common::explain::Explanation;
+use std::collections::{HashSet, VecDeque};
+use tracing::debug;
+
+pub fn get_plant_count(initial_state: &VecDeque<char>, rules: &HashSet<String>) -> i64 {
+    let mut start_index = 0;
+    let mut state = initial_state.clone();
+
+    for generation in 1..=20 {
+        start_index = pad(&mut state, start_index);
+        state = update_generation(&state, rules);
+        debug!(
+            day = 12,
+            part = 1,
+            step = generation,
+            start = start_index,
+            state = %state.iter().collect::<String>(),
+            "generation"
+        );
+    }
+
+    sum_plants(&state, start_index)
+}
+
+/// Sum of the pot numbers of every pot containing a plant after the given
+/// number of generations. The plants eventually settle into a pattern which
+/// repeats while drifting along the pots, so once that pattern is found the
+/// remaining generations are extrapolated instead of simulated.
+pub fn get_plant_sum(
+    initial_state: &VecDeque<char>,
+    rules: &HashSet<String>,
+    generations: usize,
+) -> i64 {
+    match find_plant_sums(initial_state, rules, generations) {
+        (sums, Some(cycle)) => cycle.extrapolate(&sums, generations),
+        (sums, None) => sums[generations],
+    }
+}
+
+/// Simulate generations until either the requested number of generations
+/// has been reached or the pattern of plants repeats. Returns the sum for
+/// every generation simulated along with the cycle if one was found.
+pub fn find_plant_sums(
+    initial_state: &VecDeque<char>,
+    rules: &HashSet<String>,
+    generations: usize,
+) -> (Vec<i64>, Option<Cycle>) {
+    let mut start_index = 0;
+    let mut state = initial_state.clone();
+    let mut detector = Detector::new();
+    let mut sums = vec![];
+
+    loop {
+        sums.push(sum_plants(&state, start_index));
+
+        if sums.len() > generations {
+            return (sums, None);
+        }
+
+        if let Some(cycle) = detector.observe(pattern(&state)) {
+            debug!(
+                day = 12,
+                part = 2,
+                step = detector.steps() - 1,
+                start = cycle.start,
+                length = cycle.length,
+                "pattern repeats"
+            );
+            return (sums, Some(cycle));
+        }
+
+        start_index = pad(&mut state, start_index);
+        state = update_generation(&state, rules);
+    }
+}
+
+pub fn explain_plant_sum(
+    title: &str,
+    initial_state: &VecDeque<char>,
+    rules: &HashSet<String>,
+    generations: usize,
+) -> Explanation {
+    let explanation = Explanation::new(title)
+        .fact("rules producing a plant", rules.len())
+        .fact("generations", generations);
+
+    match find_plant_sums(initial_state, rules, generations) {
+        (sums, Some(cycle)) => {
+            let drift = sums[cycle.start + cycle.length] - sums[cycle.start];
+
+            explanation
+                .fact("pattern repeats from generation", cycle.start)
+                .fact("generations per repetition", cycle.length)
+                .fact("sum drift per repetition", drift)
+                .fact("sum", cycle.extrapolate(&sums, generations))
+        }
+        (sums, None) => explanation
+            .fact("pattern repeats from generation", "not reached")
+            .fact("sum", sums[generations]),
+    }
+}
+
+pub fn sum_plants(state: &VecDeque<char>, start_index: i32) -> i64 {
+    state
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| **c == '#')
+        .map(|(index, _)| index as i64 + start_index as i64)
+        .sum()
+}
+
+/// The plants in the current state regardless of where they are positioned
+pub fn pattern(state: &VecDeque<char>) -> String {
+    state
+        .iter()
+        .collect::<String>()
+        .trim_matches('.')
+        .to_string()
+}
+
+pub fn get_rules<'a, T: Iterator<Item = &'a str>>(lines: &'a mut T) -> Option<HashSet<String>> {
+    let mut result = HashSet::new();
+
+    for line in lines {
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut tokens = line.split(" => ");
+
+        let key = tokens.next()?;
+        let value = tokens.next()?;
+
+        if value == "#" {
+            result.insert(String::from(key));
+        }
+    }
+
+    Some(result)
+}
+
+pub fn get_state(content: &str) -> Option<VecDeque<char>> {
+    let mut tokens = content.split(": ");
+    tokens.next(); // we dont care about "initial state: "
+    let state = tokens.next()?;
+
+    Some(state.chars().collect())
+}
+
+pub fn window(state: &VecDeque<char>, index: usize) -> String {
+    let mut result = vec![];
+
+    for i in -2..=2 {
+        let target: i32 = index as i32 + i;
+        if target < 0 || target as usize >= state.len() {
+            result.push('.');
+        } else {
+            result.push(state[target as usize]);
+        }
+    }
+
+    result.iter().collect()
+}
+
+pub fn pad(state: &mut VecDeque<char>, start_index: i32) -> i32 {
+    let mut start_index = start_index;
+
+    if state[0] == '#' {
+        state.push_front('.');
+        state.push_front('.');
+        state.push_front('.');
+        start_index -= 3;
+    } else if state[1] == '#' {
+        state.push_front('.');
+        state.push_front('.');
+        start_index -= 2;
+    } else if state[2] == '#' {
+        state.push_front('.');
+        start_index -= 1;
+    }
+
+    let size = state.len();
+
+    if state[size - 1] == '#' {
+        state.push_back('.');
+        state.push_back('.');
+        state.push_back('.');
+    } else if state[size - 2] == '#' {
+        state.push_back('.');
+        state.push_back('.');
+    } else if state[size - 3] == '#' {
+        state.push_back('.');
+    }
+
+    start_index
+}
+
+pub fn update_generation(state: &VecDeque<char>, rules: &HashSet<String>) -> VecDeque<char> {
+    let mut result = VecDeque::new();
+    for index in 0..state.len() {
+        let key = window(state, index);
+
+        if rules.contains(&key) {
+            result.push_back('#');
+        } else {
+            result.push_back('.');
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test_get_plant_count {
+    use super::*;
+    use rstest::*;
+
+    fn get_example() -> (VecDeque<char>, HashSet<String>) {
+        let mut lines = vec![
+            "...## => #",
+            "..#.. => #",
+            ".#... => #",
+            ".#.#. => #",
+            ".#.## => #",
+            ".##.. => #",
+            ".#### => #",
+            "#.#.# => #",
+            "#.### => #",
+            "##.#. => #",
+            "##.## => #",
+            "###.. => #",
+            "###.# => #",
+            "####. => #",
+        ]
+        .into_iter();
+
+        let rules = get_rules(&mut lines).unwrap();
+        let state = get_state("initial state: #..#.#..##......###...###").unwrap();
+
+        (state, rules)
+    }
+
+    #[test]
+    fn test_get_state() {
+        assert_eq!(
+            get_state("initial state: #.#"),
+            Some(vec!['#', '.', '#'].into())
+        );
+        assert_eq!(get_state("initial state #.#"), None);
+    }
+
+    #[test]
+    fn test_get_rules() {
+        let mut lines = vec!["..#.. => #", "", "#.... => ."].into_iter();
+        assert_eq!(
+            get_rules(&mut lines),
+            Some(HashSet::from([String::from("..#..")]))
+        );
+
+        let mut lines = vec!["..#.. => #", "#.... #"].into_iter();
+        assert_eq!(get_rules(&mut lines), None);
+    }
+
+    #[test]
+    fn test_provided_example() {
+        let (state, rules) = get_example();
+
+        let result = get_plant_count(&state, &rules);
+
+        assert_eq!(result, 325);
+    }
+
+    #[rstest(generations, case(0), case(20), case(100), case(250))]
+    fn test_extrapolated_sum(generations: usize) {
+        let (state, rules) = get_example();
+
+        let mut start_index = 0;
+        let mut expected = state.clone();
+        for _ in 0..generations {
+            start_index = pad(&mut expected, start_index);
+            expected = update_generation(&expected, &rules);
+        }
+        let expected = sum_plants(&expected, start_index);
+
+        assert_eq!(get_plant_sum(&state, &rules, generations), expected);
+    }
+
+    #[test]
+    fn test_fifty_billion_generations() {
+        let (state, rules) = get_example();
+
+        let early = get_plant_sum(&state, &rules, 1000);
+        let later = get_plant_sum(&state, &rules, 2000);
+        let result = get_plant_sum(&state, &rules, 50_000_000_000);
+
+        // once settled, the example pattern drifts by the same amount every generation
+        let drift = (later - early) / 1000;
+        assert_eq!(result, early + drift * (50_000_000_000 - 1000));
+    }
+
+    #[test]
+    fn test_explain_plant_sum() {
+        let (state, rules) = get_example();
+
+        let explanation = explain_plant_sum("example", &state, &rules, 20);
+        assert_eq!(
+            explanation.get("pattern repeats from generation"),
+            Some("not reached")
+        );
+        assert_eq!(explanation.get("sum"), Some("325"));
+
+        let explanation = explain_plant_sum("example", &state, &rules, 50_000_000_000);
+        assert_eq!(
+            explanation.get("sum").map(String::from),
+            Some(get_plant_sum(&state, &rules, 50_000_000_000).to_string())
+        );
+        assert_eq!(explanation.get("generations per repetition"), Some("1"));
+    }
+}
+
+#[cfg(test)]
+mod test_pad {
+    use super::*;
+    use rstest::*;
+
+    #[rstest(state, expected, expected_new_index,
+        case("...#...", "...#...", 0),
+        case("......", "......", 0),
+        case("#.....", "...#.....", -3),
+        case(".....#", ".....#...", 0),
+        case("....#.", "....#...", 0),
+        case("...#..", "...#...", 0),
+    )]
+    fn test_correct_output(state: &str, expected: &str, expected_new_index: i32) {
+        let mut state = state.chars().collect::<VecDeque<char>>();
+        let expected = expected.chars().collect::<VecDeque<char>>();
+
+        let new_index = pad(&mut state, 0);
+
+        assert_eq!(state, expected);
+        assert_eq!(new_index, expected_new_index);
+    }
+}
+
+#[cfg(test)]
+mod test_window {
+    use super::*;
+    use rstest::*;
+
+    #[rstest(index, expected, case(0, "....#"), case(3, ".##.."), case(12, ".##.."))]
+    fn test_correct_output(index: usize, expected: &str) {
+        let state: VecDeque<char> = "..##..#.#..##".chars().collect::<VecDeque<char>>();
+
+        assert_eq!(window(&state, index), expected);
+    }
+}
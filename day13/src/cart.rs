@@ -1,14 +1,8 @@
 use std::cmp::Ordering;
 
 use crate::track::Track;
-
-#[derive(Debug, PartialEq, Copy, Clone)]
-pub enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
+use common::direction::Direction;
+use common::reading::ReadingOrder;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Position {
@@ -42,41 +36,42 @@ impl Cart {
     }
 }
 
+impl ReadingOrder for Position {
+    fn position(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+}
+
+// carts take their turns in reading order
 impl PartialOrd for Position {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match self.y.partial_cmp(&other.y) {
-            Some(Ordering::Equal) => self.x.partial_cmp(&other.x),
-            ordering => ordering,
-        }
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Position {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self.y.cmp(&other.y) {
-            Ordering::Equal => self.x.cmp(&other.x),
-            ordering => ordering,
-        }
+        self.reading_cmp(other)
     }
 }
 
 pub fn turn(cart: &Cart, track: Track) -> (Direction, u32) {
     let mut turn_count = cart.turn_count;
     let direction = match (cart.direction, track) {
-        (Direction::North, Track::DiagonalRight) => turn_right(cart.direction),
-        (Direction::North, Track::DiagonalLeft) => turn_left(cart.direction),
-        (Direction::South, Track::DiagonalRight) => turn_right(cart.direction),
-        (Direction::South, Track::DiagonalLeft) => turn_left(cart.direction),
-        (Direction::West, Track::DiagonalLeft) => turn_right(cart.direction),
-        (Direction::West, Track::DiagonalRight) => turn_left(cart.direction),
-        (Direction::East, Track::DiagonalLeft) => turn_right(cart.direction),
-        (Direction::East, Track::DiagonalRight) => turn_left(cart.direction),
+        (Direction::North, Track::DiagonalRight) => cart.direction.turn_right(),
+        (Direction::North, Track::DiagonalLeft) => cart.direction.turn_left(),
+        (Direction::South, Track::DiagonalRight) => cart.direction.turn_right(),
+        (Direction::South, Track::DiagonalLeft) => cart.direction.turn_left(),
+        (Direction::West, Track::DiagonalLeft) => cart.direction.turn_right(),
+        (Direction::West, Track::DiagonalRight) => cart.direction.turn_left(),
+        (Direction::East, Track::DiagonalLeft) => cart.direction.turn_right(),
+        (Direction::East, Track::DiagonalRight) => cart.direction.turn_left(),
         (direction, Track::Intersection) => {
             turn_count += 1;
             match cart.turn_count % 3 {
-                0 => turn_left(direction),
+                0 => direction.turn_left(),
                 1 => direction,
-                2 => turn_right(direction),
+                2 => direction.turn_right(),
                 _ => panic!("Unreachable"),
             }
         }
@@ -86,24 +81,6 @@ pub fn turn(cart: &Cart, track: Track) -> (Direction, u32) {
     (direction, turn_count)
 }
 
-fn turn_right(direction: Direction) -> Direction {
-    match direction {
-        Direction::North => Direction::East,
-        Direction::East => Direction::South,
-        Direction::South => Direction::West,
-        Direction::West => Direction::North,
-    }
-}
-
-fn turn_left(direction: Direction) -> Direction {
-    match direction {
-        Direction::North => Direction::West,
-        Direction::West => Direction::South,
-        Direction::South => Direction::East,
-        Direction::East => Direction::North,
-    }
-}
-
 #[cfg(test)]
 mod test_position {
     use super::*;
@@ -0,0 +1,15 @@
+// https://adventofcode.com/2018/day/13
+
+use common::explain::Explanation;
+use map::Map;
+
+pub mod cart;
+pub mod map;
+pub mod track;
+
+pub fn explain_simulation(title: &str, carts: usize, map: &Map) -> Explanation {
+    Explanation::new(title)
+        .fact("carts at start", carts)
+        .fact("carts remaining", map.carts.len())
+        .fact("ticks", map.ticks)
+}
@@ -1,31 +1,69 @@
 // https://adventofcode.com/2018/day/13
 
-mod cart;
-mod map;
-mod track;
-
-use map::Map;
-
 use anyhow::Result;
-use std::io::Read;
+use common::cli::Options;
+use common::input::{read_stdin, Whitespace};
+use common::report::Report;
+use day13::cart::Position;
+use day13::explain_simulation;
+use day13::map::{Map, MapError};
 
 fn main() -> Result<()> {
-    let mut contents = String::new();
+    common::logging::init();
+
+    let options = Options::from_args()?;
+    let mut report = Report::new(13);
 
-    std::io::stdin().read_to_string(&mut contents)?;
+    let contents = read_stdin(Whitespace::Significant)?;
 
     let mut map: Map = contents.parse()?;
 
     println!("Track Size: {}x{}", map.tracks[0].len(), map.tracks.len());
     println!("Carts: {}", map.carts.len());
 
-    println!("Running cart simulation");
-    let position = map.run_until_collission(false, 400)?;
-    println!("{:?}", position);
+    let carts = map.carts.len();
+
+    // part 2 carries on from where part 1 stopped, or from
+    // the start when only part 2 is being solved
+    if options.runs(1) {
+        println!("Running cart simulation");
+        let position = report.time(1, || map.run_until_collission(400))?;
+        report.answer(1, format_position(&position));
+        println!("{:?}", position);
+
+        if options.explain {
+            let explanation =
+                explain_simulation("Part 1: location of the first crash", carts, &map)
+                    .fact("crash", format_position(&position));
+            print!("{}", explanation);
+        }
+    }
 
-    println!("Continuing cart simulation");
-    let position = map.run_until_last_cart(false, 100000)?;
-    println!("{:?}", position);
+    if options.runs(2) {
+        println!("Continuing cart simulation");
+        match report.time(2, || map.run_until_last_cart(100000)) {
+            Ok(position) => {
+                report.answer(2, format_position(&position));
+                println!("{:?}", position);
+
+                if options.explain {
+                    let explanation =
+                        explain_simulation("Part 2: location of the last cart", carts, &map)
+                            .fact("last cart", format_position(&position));
+                    print!("{}", explanation);
+                }
+            }
+            // maps with an even number of carts have no answer for part 2
+            Err(MapError::NoCartsLeft) => println!("{}", MapError::NoCartsLeft),
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    report.write(&options)?;
 
     Ok(())
 }
+
+fn format_position(position: &Position) -> String {
+    format!("{},{}", position.x, position.y)
+}
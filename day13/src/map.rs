@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 
-use crate::cart::{turn, Cart, Direction, Position};
+use crate::cart::{turn, Cart, Position};
 use crate::track::Track;
+use common::cycle::Detector;
+use common::direction::Direction;
 use thiserror::Error;
+use tracing::{debug, trace};
 
 #[derive(Error, Debug)]
 pub enum MapError {
@@ -10,12 +13,20 @@ pub enum MapError {
     UnknownTrack(char),
     #[error("Simulation ran past limit")]
     RanPastLimit,
+    #[error("Simulation repeats every {1} ticks from tick {0}")]
+    Repeating(usize, usize),
+    #[error("Cart at {0},{1} ran off the tracks")]
+    OffTrack(usize, usize),
+    #[error("Every cart crashed, so there is no last cart")]
+    NoCartsLeft,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Map {
     pub carts: Vec<Cart>,
     pub tracks: Vec<Vec<Track>>,
+    /// Number of ticks simulated so far
+    pub ticks: usize,
 }
 
 impl Map {
@@ -40,8 +51,18 @@ impl Map {
             .join("\n")
     }
 
-    pub fn get_track(&self, x: usize, y: usize) -> Track {
-        self.tracks[y][x]
+    /// The track at the given point, if the point is on the map
+    pub fn get_track(&self, x: usize, y: usize) -> Option<Track> {
+        self.tracks.get(y)?.get(x).copied()
+    }
+
+    /// Everything about the carts which affects how the simulation plays out.
+    /// If the same fingerprint is seen twice, the simulation will loop forever
+    fn fingerprint(&self) -> Vec<(Position, Direction, u32)> {
+        self.carts
+            .iter()
+            .map(|c| (c.position.clone(), c.direction, c.turn_count % 3))
+            .collect()
     }
 
     pub fn check_collisions(&self, dead: &[usize]) -> Option<(usize, usize)> {
@@ -76,14 +97,13 @@ impl Map {
             let x = cart.position.x;
             let y = cart.position.y;
 
-            let position = match cart.direction {
-                Direction::North => Position { x, y: y - 1 },
-                Direction::South => Position { x, y: y + 1 },
-                Direction::West => Position { x: x - 1, y },
-                Direction::East => Position { x: x + 1, y },
-            };
-            let track = self.get_track(position.x, position.y);
-            let (direction, turn_count) = turn(&cart, track);
+            let (x, y) = cart
+                .direction
+                .step((x, y))
+                .ok_or(MapError::OffTrack(x, y))?;
+            let position = Position { x, y };
+            let track = self.get_track(x, y).ok_or(MapError::OffTrack(x, y))?;
+            let (direction, turn_count) = turn(cart, track);
 
             self.carts[index] = Cart {
                 position,
@@ -115,38 +135,62 @@ impl Map {
         // each of these loops is called a tick.
         // TODO: There must be a better way to do this without cloning position
         self.carts.sort_by_cached_key(|c| c.position.clone());
+        self.ticks += 1;
         Ok(crashes)
     }
 
-    pub fn run_until_collission(
-        &mut self,
-        print: bool,
-        limit: usize,
-    ) -> Result<Position, MapError> {
-        for _ in 0..limit {
-            if print {
-                println!("{}\n", self.print());
+    pub fn run_until_collission(&mut self, limit: usize) -> Result<Position, MapError> {
+        let mut detector = Detector::new();
+
+        for tick in 0..limit {
+            if let Some(cycle) = detector.observe(self.fingerprint()) {
+                return Err(MapError::Repeating(cycle.start, cycle.length));
             }
 
+            trace!(day = 13, part = 1, step = tick, map = %self.print(), "tick");
+
             let crashes = self.run()?;
 
             if !crashes.is_empty() {
+                debug!(
+                    day = 13,
+                    part = 1,
+                    step = tick,
+                    x = crashes[0].x,
+                    y = crashes[0].y,
+                    "crash"
+                );
                 return Ok(crashes[0].clone());
             }
         }
         Err(MapError::RanPastLimit)
     }
 
-    pub fn run_until_last_cart(&mut self, print: bool, limit: usize) -> Result<Position, MapError> {
-        for _ in 0..limit {
-            if print {
-                println!("{}\n", self.print());
+    pub fn run_until_last_cart(&mut self, limit: usize) -> Result<Position, MapError> {
+        let mut detector = Detector::new();
+
+        for tick in 0..limit {
+            if let Some(cycle) = detector.observe(self.fingerprint()) {
+                return Err(MapError::Repeating(cycle.start, cycle.length));
             }
 
-            self.run()?;
+            trace!(day = 13, part = 2, step = tick, map = %self.print(), "tick");
+
+            for crash in self.run()? {
+                debug!(
+                    day = 13,
+                    part = 2,
+                    step = tick,
+                    x = crash.x,
+                    y = crash.y,
+                    "crash"
+                );
+            }
 
-            if self.carts.len() == 1 {
-                return Ok(self.carts[0].position.clone());
+            match self.carts.len() {
+                0 => return Err(MapError::NoCartsLeft),
+                1 => return Ok(self.carts[0].position.clone()),
+                _ => {}
             }
         }
         Err(MapError::RanPastLimit)
@@ -199,7 +243,11 @@ impl std::str::FromStr for Map {
             tracks.push(row);
         }
 
-        Ok(Map { tracks, carts })
+        Ok(Map {
+            tracks,
+            carts,
+            ticks: 0,
+        })
     }
 }
 
@@ -219,7 +267,6 @@ mod test_map {
             }
         }
 
-        // TODO: Fix this infinite loop
         // extra example found on Reddit
         // https://www.reddit.com/r/adventofcode/comments/a8f32j/2018_day_13_help_needed/#t1_ecdqxrx
         #[test]
@@ -233,13 +280,46 @@ mod test_map {
             .join("\n")
             .parse()?;
 
-            let position = map.run_until_collission(true, 10)?;
+            let position = map.run_until_collission(10)?;
 
             assert_eq!(position, Position { x: 0, y: 1 });
 
             Ok(())
         }
 
+        #[test]
+        fn test_off_track() -> Result<(), MapError> {
+            // carts heading straight off the edge of the map
+            for contents in &["^", "|\n|\nv", "<-", "->"] {
+                let mut map: Map = contents.parse()?;
+
+                let result = map.run();
+                assert!(matches!(result, Err(MapError::OffTrack(_, _))));
+            }
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_no_collision() -> Result<(), MapError> {
+            // two carts going round their own separate loops
+            #[rustfmt::skip]
+            let map: Map = [
+                r"/>\/<\",
+                r"\-/\-/",
+            ]
+            .join("\n")
+            .parse()?;
+
+            let result = map.clone().run_until_collission(100);
+            assert!(matches!(result, Err(MapError::Repeating(0, 6))));
+
+            let result = map.clone().run_until_last_cart(100);
+            assert!(matches!(result, Err(MapError::Repeating(0, 6))));
+
+            Ok(())
+        }
+
         #[test]
         fn test_provided_example_1() -> Result<(), MapError> {
             let mut map: Map = [
@@ -279,9 +359,10 @@ mod test_map {
             .parse()?;
             assert_cart_positions_eq(&map, &expected);
 
-            let position = map.run_until_collission(false, 15)?;
+            let position = map.run_until_collission(15)?;
 
             assert_eq!(position, Position { x: 7, y: 3 });
+            assert_eq!(map.ticks, 14);
 
             Ok(())
         }
@@ -294,9 +375,21 @@ mod test_map {
             .join("\n")
             .parse()?;
 
-            let position = map.run_until_last_cart(true, 10)?;
+            let position = map.run_until_last_cart(10)?;
 
             assert_eq!(position, Position { x: 6, y: 4 });
+            assert_eq!(map.ticks, 3);
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_no_carts_left() -> Result<(), MapError> {
+            // an even number of carts can all crash into each other
+            let mut map: Map = "->-<-".parse()?;
+
+            let result = map.run_until_last_cart(10);
+            assert!(matches!(result, Err(MapError::NoCartsLeft)));
 
             Ok(())
         }
@@ -314,6 +407,7 @@ mod test_map {
                     Cart::new(1, 0, Direction::North),
                     Cart::new(1, 1, Direction::South),
                 ],
+                ticks: 0,
             };
 
             assert_eq!(map.check_collisions(&[]), None);
@@ -328,6 +422,7 @@ mod test_map {
                     Cart::new(1, 1, Direction::North),
                     Cart::new(1, 1, Direction::South),
                 ],
+                ticks: 0,
             };
 
             let expected = (2, 1);
@@ -401,19 +496,37 @@ mod test_map {
 
             // it would be too cumbersome testing the entire
             // map, so instead we can just sample it
-            assert_eq!(map.get_track(0, 0), Track::DiagonalRight);
-            assert_eq!(map.get_track(1, 0), Track::Horizontal);
-            assert_eq!(map.get_track(0, 1), Track::Vertical);
-            assert_eq!(map.get_track(6, 0), Track::DiagonalLeft);
-            assert_eq!(map.get_track(7, 0), Track::Empty);
-            assert_eq!(map.get_track(6, 1), Track::Vertical);
-            assert_eq!(map.get_track(6, 2), Track::Intersection);
+            assert_eq!(map.get_track(0, 0), Some(Track::DiagonalRight));
+            assert_eq!(map.get_track(1, 0), Some(Track::Horizontal));
+            assert_eq!(map.get_track(0, 1), Some(Track::Vertical));
+            assert_eq!(map.get_track(6, 0), Some(Track::DiagonalLeft));
+            assert_eq!(map.get_track(7, 0), Some(Track::Empty));
+            assert_eq!(map.get_track(6, 1), Some(Track::Vertical));
+            assert_eq!(map.get_track(6, 2), Some(Track::Intersection));
 
             // Test the track values at cart locations
-            assert_eq!(map.get_track(0, 2), Track::Vertical);
-            assert_eq!(map.get_track(1, 4), Track::Horizontal);
-            assert_eq!(map.get_track(9, 4), Track::Vertical);
-            assert_eq!(map.get_track(7, 6), Track::Horizontal);
+            assert_eq!(map.get_track(0, 2), Some(Track::Vertical));
+            assert_eq!(map.get_track(1, 4), Some(Track::Horizontal));
+            assert_eq!(map.get_track(9, 4), Some(Track::Vertical));
+            assert_eq!(map.get_track(7, 6), Some(Track::Horizontal));
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_normalised_input() -> Result<(), MapError> {
+            use common::input::{normalise, Whitespace};
+
+            let lines = [r"  /->", r"  \-</"];
+            let expected: Map = lines.join("\n").parse()?;
+
+            // a CRLF file with a byte order mark and a trailing blank line
+            let contents = format!("\u{feff}{}\r\n\r\n", lines.join("\r\n"));
+            let map: Map = normalise(&contents, Whitespace::Significant).parse()?;
+
+            assert_eq!(map.tracks, expected.tracks);
+            assert_eq!(map.carts, expected.carts);
+            assert_eq!(map.get_track(0, 0), Some(Track::Empty));
 
             Ok(())
         }
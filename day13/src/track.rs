@@ -9,7 +9,7 @@ pub enum Track {
 }
 
 impl Track {
-    pub fn to_char(&self) -> char {
+    pub fn to_char(self) -> char {
         match self {
             Track::Intersection => '+',
             Track::Horizontal => '-',
@@ -1,10 +1,15 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
+use common::direction;
+use common::reading::{self, ReadingOrder};
+use common::search::{self, Search};
+
 use crate::point::Point;
 use crate::race::Race;
 use crate::unit::Unit;
 use thiserror::Error;
+use tracing::{debug, trace};
 
 type Path = Vec<Point>;
 
@@ -79,108 +84,52 @@ impl Game {
     /// adjacent
     /// TODO: Can we remove filter_units? Feels hacky
     fn get_adjacent(&self, point: &Point, filter_units: bool) -> Vec<Point> {
-        let mut result = Vec::with_capacity(4);
-
-        // The order of checks below is purposefully done
-        // in reading order
-
-        if point.y > 0 {
-            result.push(Point {
-                x: point.x,
-                y: point.y - 1,
-            });
-        }
-
-        if point.x > 0 {
-            result.push(Point {
-                x: point.x - 1,
-                y: point.y,
-            });
-        }
-
-        if point.x + 1 < self.width() {
-            result.push(Point {
-                x: point.x + 1,
-                y: point.y,
-            });
-        }
-
-        if point.y + 1 < self.height() {
-            result.push(Point {
-                x: point.x,
-                y: point.y + 1,
-            });
-        }
+        // neighbours are given in reading order
+        let result = direction::neighbours((point.x, point.y), self.width(), self.height())
+            .map(|(x, y)| Point { x, y });
 
         if filter_units {
-            result
-                .into_iter()
-                .filter(|p| self.is_position_free(&p))
-                .collect()
+            result.filter(|p| self.is_position_free(p)).collect()
         } else {
-            result
+            result.collect()
         }
     }
 
-    /// Perform a breadth first search on the map given two points
+    /// Perform a breadth first search over the free positions of the map
+    /// starting from the given point. Ties are broken in reading order.
+    fn search_from(&self, point: &Point) -> Search<Point> {
+        search::bfs(*point, |p| self.get_adjacent(p, true), reading::compare)
+    }
+
+    /// Distance from the given point to every position on the map.
+    /// Positions which cannot be reached have a distance of u32::MAX
+    #[cfg(test)]
     fn breadth_first_search(&self, point: &Point) -> Vec<Vec<u32>> {
-        // initialise all the necessary variables
         let mut weights = vec![vec![u32::MAX; self.width()]; self.height()];
 
-        let mut visited = HashSet::new();
-        visited.insert(*point);
-        weights[point.y][point.x] = 0;
-
-        let mut queue = VecDeque::new();
-        for next in self.get_adjacent(point, true) {
-            weights[next.y][next.x] = 1;
-            queue.push_back(next);
-        }
-
-        // start populating the weights
-        while let Some(next) = queue.pop_front() {
-            visited.insert(next);
-
-            for point in self.get_adjacent(&next, true) {
-                if !visited.contains(&point) && !queue.contains(&point) {
-                    weights[point.y][point.x] = weights[next.y][next.x] + 1;
-                    queue.push_back(point);
-                }
-            }
+        for (point, distance) in self.search_from(point).iter() {
+            weights[point.y][point.x] = *distance;
         }
 
         weights
     }
 
+    /// Shortest path from point_a to point_b, excluding point_a itself.
+    /// When there are multiple shortest paths, the one whose steps come
+    /// first in reading order is chosen.
     fn get_path(&self, point_a: &Point, point_b: &Point) -> Option<Path> {
-        let weights = self.breadth_first_search(&point_b);
-        let mut result = vec![];
-        let mut current = *point_a;
-
-        while &current != point_b {
-            let next = self
-                .get_adjacent(&current, true)
-                .into_iter()
-                .filter(|p| !result.contains(p))
-                // From the rust docs:
-                // If several elements are equally minimum, the first element is returned
-                .min_by_key(|p| weights[p.y][p.x]);
-
-            if let Some(next) = next {
-                current = next;
-                result.push(current);
-            } else {
-                // Unable to reach destination
-                // Treat the turn as a skip
-                return None;
-            }
+        if point_a == point_b {
+            return Some(vec![]);
         }
 
-        Some(result)
+        let search = self.search_from(point_b);
+        let next = search.nearest(self.get_adjacent(point_a, true))?;
+
+        search.path_from(&next)
     }
 
     fn is_position_free(&self, position: &Point) -> bool {
-        self.terrain[position.y][position.x] == Terrain::Open && !self.units.contains_key(&position)
+        self.terrain[position.y][position.x] == Terrain::Open && !self.units.contains_key(position)
     }
 
     fn get_adjacent_target(&self, point: &Point, race: Race) -> Option<Point> {
@@ -188,23 +137,14 @@ impl Game {
         // by being immediately adjacent to it. If there are no such targets, the unit ends its turn.
         // Otherwise, the adjacent target with the fewest hit points is selected; in a tie,
         // the adjacent target with the fewest hit points which is first in reading order is selected.
-        let mut min_hit_points = u32::MAX;
-        let mut result = None;
-        for position in self.get_adjacent(point, false) {
-            if let Some(unit) = self.units.get(&position) {
-                if unit.race == race {
-                    if result.is_none()
-                        || unit.hit_points < min_hit_points
-                        || (unit.hit_points == min_hit_points && position < result.unwrap())
-                    {
-                        result = Some(position);
-                        min_hit_points = unit.hit_points;
-                    }
-                }
-            }
-        }
-
-        result
+        self.get_adjacent(point, false)
+            .into_iter()
+            .filter_map(|position| {
+                let unit = self.units.get(&position).filter(|u| u.race == race)?;
+                Some((unit.hit_points, position.reading_key(), position))
+            })
+            .min_by_key(|(hit_points, key, _)| (*hit_points, *key))
+            .map(|(_, _, position)| position)
     }
 
     /// Retrieves all units of the specified race who still are
@@ -215,7 +155,7 @@ impl Game {
 
         for (position, unit) in &self.units {
             if unit.race == race {
-                let adjacent = self.get_adjacent(&position, true);
+                let adjacent = self.get_adjacent(position, true);
                 results.extend(adjacent);
             }
         }
@@ -243,27 +183,18 @@ impl Game {
 
     fn get_next_step(&self, point: &Point, race: Race) -> Option<Point> {
         // First calculate distance to all points
-        let weights = self.breadth_first_search(&point);
+        let search = self.search_from(point);
 
         let free_positions = self.get_free_positions(race.enemy());
-        let target = free_positions.iter().min_by_key(|p| weights[p.y][p.x]);
-
-        if let Some(point_b) = target {
-            if let Some(mut path) = self.get_path(&point, &point_b) {
-                // is there a nicer way of doing this?
-                // I just want to remove the first element in the list
-                // without having to need to use a VecDequeue
-                path.reverse();
-                return path.pop();
-            }
-        }
+        let target = search.nearest(free_positions)?;
 
-        None
+        let path = self.get_path(point, &target)?;
+        path.first().copied()
     }
 
     /// Checks if the end condition for the game has been satisfied
     /// The game ends when there is only one remaining race left on the board
-    fn game_completed(&self) -> bool {
+    pub fn game_completed(&self) -> bool {
         let unique_units = self.units.values().map(|u| u.race).collect::<HashSet<_>>();
         unique_units.len() <= 1
     }
@@ -272,11 +203,23 @@ impl Game {
         self.units.values().map(|u| u.hit_points).sum()
     }
 
+    /// Number of units of the given race still on the board
+    pub fn count(&self, race: Race) -> usize {
+        self.units.values().filter(|u| u.race == race).count()
+    }
+
+    /// The race left standing once the game has completed
+    pub fn winner(&self) -> Option<Race> {
+        if self.game_completed() {
+            self.units.values().next().map(|u| u.race)
+        } else {
+            None
+        }
+    }
+
     pub fn play(&mut self, max_rounds: u64) -> u64 {
         let mut index = 0;
-        println!();
-        println!("Initial");
-        println!("{}", self.render_map());
+        trace!(day = 15, part = 1, step = index, map = %self.render_map(), "initial");
 
         loop {
             if index >= max_rounds {
@@ -294,8 +237,16 @@ impl Game {
             if completed {
                 index += 1;
             }
-            println!("Round: {}", index);
-            println!("{}", self.render_map());
+            debug!(
+                day = 15,
+                part = 1,
+                step = index,
+                completed,
+                units = self.units.len(),
+                hit_points = self.total_hp_remaining(),
+                "round"
+            );
+            trace!(day = 15, part = 1, step = index, map = %self.render_map(), "round");
 
             if self.game_completed() {
                 return index;
@@ -303,6 +254,12 @@ impl Game {
         }
     }
 
+    /// Plays a single round. Returns true if every unit got to act,
+    /// which is how full rounds are counted towards the outcome
+    pub fn round(&mut self) -> bool {
+        self.next()
+    }
+
     fn next(&mut self) -> bool {
         // we copy here to prevent an immutable borrow from allowing us to
         // mutate the contents in the loop below
@@ -319,8 +276,8 @@ impl Game {
             if let Some(unit) = self.units.get(&point) {
                 let unit = unit.clone();
                 if let Some(enemy_position) = self.get_adjacent_target(&point, unit.race.enemy()) {
-                    let mut enemy = self.units.get_mut(&enemy_position).unwrap();
-                    unit.attack(&mut enemy);
+                    let enemy = self.units.get_mut(&enemy_position).unwrap();
+                    unit.attack(enemy);
 
                     if !enemy.is_alive() {
                         self.units.remove(&enemy_position);
@@ -333,8 +290,8 @@ impl Game {
                     // TODO: This repeated code, find a nicer way of doing this!
                     if let Some(enemy_position) = self.get_adjacent_target(&step, unit.race.enemy())
                     {
-                        let mut enemy = self.units.get_mut(&enemy_position).unwrap();
-                        unit.attack(&mut enemy);
+                        let enemy = self.units.get_mut(&enemy_position).unwrap();
+                        unit.attack(enemy);
 
                         if !enemy.is_alive() {
                             self.units.remove(&enemy_position);
@@ -413,6 +370,9 @@ mod test_game {
         let total_hp = game.total_hp_remaining();
 
         assert_eq!((rounds, total_hp), (47, 590));
+        assert_eq!(game.winner(), Some(Race::Goblin));
+        assert_eq!(game.count(Race::Goblin), 4);
+        assert_eq!(game.count(Race::Elf), 0);
 
         Ok(())
     }
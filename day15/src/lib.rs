@@ -0,0 +1,6 @@
+// https://adventofcode.com/2018/day/15
+
+pub mod game;
+pub mod point;
+pub mod race;
+pub mod unit;
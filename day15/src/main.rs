@@ -1,29 +1,60 @@
 // https://adventofcode.com/2018/day/15
 
-mod game;
-mod point;
-mod race;
-mod unit;
-
-use game::Game;
+use common::cli::Options;
+use common::explain::Explanation;
+use common::input::{read_stdin, Whitespace};
+use common::report::Report;
+use day15::game::Game;
+use day15::race::Race;
 use std::error::Error;
-use std::io::Read;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let mut contents = String::new();
+    common::logging::init();
+
+    let options = Options::from_args()?;
+    let mut report = Report::new(15);
 
-    std::io::stdin().read_to_string(&mut contents)?;
+    let contents = read_stdin(Whitespace::Significant)?;
 
     let mut game: Game = contents.parse()?;
-    let rounds = game.play(u64::MAX);
-    let total_hp = game.total_hp_remaining();
+    let elves = game.count(Race::Elf);
+    let goblins = game.count(Race::Goblin);
+
+    if options.runs(1) {
+        let rounds = report.time(1, || game.play(u64::MAX));
+        let total_hp = game.total_hp_remaining();
+        report.answer(1, rounds * total_hp as u64);
+
+        // "You need to determine the outcome of the battle:
+        // the number of full rounds that were completed (not counting
+        // the round in which combat ends) multiplied by the sum of
+        // the hit points of all remaining units at the moment combat ends"
+        println!("Rounds: {}, Total HP: {}", rounds, total_hp);
+        println!("Result: {}", rounds * total_hp as u64);
 
-    // "You need to determine the outcome of the battle:
-    // the number of full rounds that were completed (not counting
-    // the round in which combat ends) multiplied by the sum of
-    // the hit points of all remaining units at the moment combat ends"
-    println!("Rounds: {}, Total HP: {}", rounds, total_hp);
-    println!("Result: {}", rounds * total_hp as u64);
+        if options.explain {
+            let explanation =
+                Explanation::new("Outcome: full rounds multiplied by hit points left")
+                    .fact("elves at start", elves)
+                    .fact("goblins at start", goblins)
+                    .fact("winner", describe_winner(game.winner()))
+                    .fact("units remaining", game.units.len())
+                    .fact("full rounds", rounds)
+                    .fact("hit points remaining", total_hp)
+                    .fact("answer", format!("{} * {}", rounds, total_hp));
+            print!("{}", explanation);
+        }
+    }
+
+    report.write(&options)?;
 
     Ok(())
 }
+
+fn describe_winner(race: Option<Race>) -> &'static str {
+    match race {
+        Some(Race::Elf) => "elves",
+        Some(Race::Goblin) => "goblins",
+        None => "nobody",
+    }
+}
@@ -1,3 +1,4 @@
+use common::reading::ReadingOrder;
 use std::cmp::Ordering;
 
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
@@ -6,6 +7,13 @@ pub struct Point {
     pub y: usize,
 }
 
+impl ReadingOrder for Point {
+    fn position(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+}
+
+// units take their turns, and break ties, in reading order
 impl PartialOrd for Point {
     fn partial_cmp(&self, other: &Point) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -14,10 +22,7 @@ impl PartialOrd for Point {
 
 impl Ord for Point {
     fn cmp(&self, other: &Point) -> Ordering {
-        match self.y.cmp(&other.y) {
-            Ordering::Equal => self.x.cmp(&other.x),
-            ordering => ordering,
-        }
+        self.reading_cmp(other)
     }
 }
 
@@ -55,3 +60,28 @@ mod test_point {
         assert_eq!(positions, expected);
     }
 }
+
+#[cfg(test)]
+mod test_point_properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn point() -> impl Strategy<Value = Point> {
+        (0..8usize, 0..8usize).prop_map(|(x, y)| Point { x, y })
+    }
+
+    proptest! {
+        #[test]
+        fn total_order(a in point(), b in point(), c in point()) {
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            if a <= b && b <= c {
+                prop_assert!(a <= c);
+            }
+        }
+
+        #[test]
+        fn consistent_with_equality(a in point(), b in point()) {
+            prop_assert_eq!(a.cmp(&b) == Ordering::Equal, a == b);
+        }
+    }
+}
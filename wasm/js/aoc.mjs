@@ -0,0 +1,95 @@
+// Wraps the exports of the wasm module so that days can be solved
+// from JavaScript with plain strings.
+//
+//   const aoc = await load(fetch("wasm.wasm"));
+//   aoc.solve(1, 2, input); // => { ok: true, output: "..." }
+
+const encoder = new TextEncoder();
+const decoder = new TextDecoder();
+
+export async function load(source) {
+  const { instance } = await WebAssembly.instantiateStreaming(source);
+  return new Solver(instance.exports);
+}
+
+export class Solver {
+  constructor(exports) {
+    this.exports = exports;
+  }
+
+  output() {
+    const { memory, aoc_output_ptr, aoc_output_len } = this.exports;
+    const bytes = new Uint8Array(memory.buffer, aoc_output_ptr(), aoc_output_len());
+    return decoder.decode(bytes);
+  }
+
+  // Copies the text into the module's memory for the duration of the call
+  withInput(text, call) {
+    const bytes = encoder.encode(text);
+    const ptr = this.exports.aoc_alloc(bytes.length);
+    new Uint8Array(this.exports.memory.buffer, ptr, bytes.length).set(bytes);
+    try {
+      return call(ptr, bytes.length);
+    } finally {
+      this.exports.aoc_dealloc(ptr, bytes.length);
+    }
+  }
+
+  parse(day, input) {
+    const status = this.withInput(input, (ptr, len) => this.exports.aoc_parse(day, ptr, len));
+    return { ok: status === 0, output: this.output() };
+  }
+
+  solve(day, part, input) {
+    const status = this.withInput(input, (ptr, len) =>
+      this.exports.aoc_solve(day, part, ptr, len)
+    );
+    return { ok: status === 0, output: this.output() };
+  }
+
+  // Only days 13 and 15 can be simulated a step at a time
+  simulate(day, input) {
+    const handle = this.withInput(input, (ptr, len) =>
+      this.exports.aoc_simulation_new(day, ptr, len)
+    );
+    if (handle === 0) {
+      throw new Error(this.output());
+    }
+    return new Simulation(this, handle);
+  }
+}
+
+export class Simulation {
+  constructor(solver, handle) {
+    this.solver = solver;
+    this.handle = handle;
+  }
+
+  // Returns false once the simulation has finished
+  step() {
+    const status = this.solver.exports.aoc_simulation_step(this.handle);
+    if (status < 0) {
+      throw new Error(this.solver.output());
+    }
+    return status === 1;
+  }
+
+  steps() {
+    return this.solver.exports.aoc_simulation_steps(this.handle);
+  }
+
+  render() {
+    this.solver.exports.aoc_simulation_render(this.handle);
+    return this.solver.output();
+  }
+
+  summary() {
+    this.solver.exports.aoc_simulation_summary(this.handle);
+    return this.solver.output();
+  }
+
+  free() {
+    this.solver.exports.aoc_simulation_free(this.handle);
+    this.handle = 0;
+  }
+}
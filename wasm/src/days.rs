@@ -0,0 +1,455 @@
+//! How each day is solved, mirroring what the day's own binary does with
+//! its input. Parts which the binaries do not solve report `Unsolved`.
+use crate::BindingError;
+use common::input::{normalise, Whitespace};
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Display;
+use std::str::FromStr;
+
+pub trait Day {
+    const NUMBER: u32;
+    const WHITESPACE: Whitespace = Whitespace::Insignificant;
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, BindingError>;
+
+    fn part1(input: Self::Input) -> Result<String, BindingError>;
+
+    fn part2(_input: Self::Input) -> Result<String, BindingError> {
+        Err(BindingError::Unsolved(Self::NUMBER, 2))
+    }
+}
+
+pub fn parse(day: u32, input: &str) -> Result<(), BindingError> {
+    match day {
+        1 => parse_day::<Day01>(input),
+        2 => parse_day::<Day02>(input),
+        3 => parse_day::<Day03>(input),
+        4 => parse_day::<Day04>(input),
+        5 => parse_day::<Day05>(input),
+        6 => parse_day::<Day06>(input),
+        7 => parse_day::<Day07>(input),
+        8 => parse_day::<Day08>(input),
+        9 => parse_day::<Day09>(input),
+        10 => parse_day::<Day10>(input),
+        11 => parse_day::<Day11>(input),
+        12 => parse_day::<Day12>(input),
+        13 => parse_day::<Day13>(input),
+        14 => parse_day::<Day14>(input),
+        15 => parse_day::<Day15>(input),
+        _ => Err(BindingError::UnknownDay(day)),
+    }
+}
+
+pub fn solve(day: u32, part: u32, input: &str) -> Result<String, BindingError> {
+    match day {
+        1 => solve_day::<Day01>(part, input),
+        2 => solve_day::<Day02>(part, input),
+        3 => solve_day::<Day03>(part, input),
+        4 => solve_day::<Day04>(part, input),
+        5 => solve_day::<Day05>(part, input),
+        6 => solve_day::<Day06>(part, input),
+        7 => solve_day::<Day07>(part, input),
+        8 => solve_day::<Day08>(part, input),
+        9 => solve_day::<Day09>(part, input),
+        10 => solve_day::<Day10>(part, input),
+        11 => solve_day::<Day11>(part, input),
+        12 => solve_day::<Day12>(part, input),
+        13 => solve_day::<Day13>(part, input),
+        14 => solve_day::<Day14>(part, input),
+        15 => solve_day::<Day15>(part, input),
+        _ => Err(BindingError::UnknownDay(day)),
+    }
+}
+
+fn parse_day<D: Day>(input: &str) -> Result<(), BindingError> {
+    D::parse(&normalise(input, D::WHITESPACE)).map(|_| ())
+}
+
+fn solve_day<D: Day>(part: u32, input: &str) -> Result<String, BindingError> {
+    let input = D::parse(&normalise(input, D::WHITESPACE))?;
+
+    match part {
+        1 => D::part1(input),
+        2 => D::part2(input),
+        _ => Err(BindingError::UnknownPart(part)),
+    }
+}
+
+pub(crate) fn invalid<E: Display>(err: E) -> BindingError {
+    BindingError::InvalidInput(err.to_string())
+}
+
+/// Parses every line of the input on its own
+fn parse_lines<T>(input: &str) -> Result<Vec<T>, BindingError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    input
+        .lines()
+        .map(|line| line.parse().map_err(invalid))
+        .collect()
+}
+
+pub struct Day01;
+
+impl Day for Day01 {
+    const NUMBER: u32 = 1;
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String, BindingError> {
+        day1::get_resulting_frequency(input).map_err(invalid)?;
+        Ok(input.to_string())
+    }
+
+    fn part1(input: String) -> Result<String, BindingError> {
+        let drift = day1::get_resulting_frequency(&input).map_err(invalid)?;
+        Ok(drift.to_string())
+    }
+
+    fn part2(input: String) -> Result<String, BindingError> {
+        let repeat = day1::find_repeat_frequency(&input)
+            .map_err(invalid)?
+            .ok_or(BindingError::NoAnswer("no frequency is reached twice"))?;
+        Ok(repeat.frequency.to_string())
+    }
+}
+
+pub struct Day02;
+
+impl Day for Day02 {
+    const NUMBER: u32 = 2;
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String, BindingError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: String) -> Result<String, BindingError> {
+        let boxes = input.split_whitespace().collect::<Vec<&str>>();
+        day2::get_solution_1(&boxes)
+            .map(|checksum| checksum.to_string())
+            .ok_or_else(|| invalid("Checksum is too large"))
+    }
+
+    fn part2(input: String) -> Result<String, BindingError> {
+        let boxes = input.split_whitespace().collect::<Vec<&str>>();
+        day2::find_similar_box_id(&boxes)
+            .map_err(invalid)?
+            .ok_or(BindingError::NoAnswer(
+                "no two box IDs differ by one character",
+            ))
+    }
+}
+
+pub struct Day03;
+
+impl Day for Day03 {
+    const NUMBER: u32 = 3;
+
+    type Input = Vec<day3pt1::rect::Rect>;
+
+    fn parse(input: &str) -> Result<Self::Input, BindingError> {
+        parse_lines(input)
+    }
+
+    fn part1(rects: Self::Input) -> Result<String, BindingError> {
+        Ok(day3pt1::Fabric::new(&rects).overlapping().to_string())
+    }
+
+    fn part2(rects: Self::Input) -> Result<String, BindingError> {
+        let fabric = day3pt1::Fabric::new(&rects);
+        rects
+            .iter()
+            .find(|rect| fabric.is_intact(rect))
+            .map(|rect| rect.id.to_string())
+            .ok_or(BindingError::NoAnswer("every claim overlaps another"))
+    }
+}
+
+pub struct Day04;
+
+impl Day for Day04 {
+    const NUMBER: u32 = 4;
+
+    type Input = Vec<day4::entry::Entry>;
+
+    fn parse(input: &str) -> Result<Self::Input, BindingError> {
+        let mut entries: Self::Input = parse_lines(input)?;
+        entries.sort_unstable();
+        Ok(entries)
+    }
+
+    fn part1(entries: Self::Input) -> Result<String, BindingError> {
+        day4::strategy_1(&entries)
+            .map(|(guard, minute)| (guard.0 as i64 * minute).to_string())
+            .ok_or(BindingError::NoAnswer("no guard fell asleep"))
+    }
+
+    fn part2(entries: Self::Input) -> Result<String, BindingError> {
+        day4::strategy_2(&entries)
+            .map(|(guard, minute)| (guard.0 as i64 * minute).to_string())
+            .ok_or(BindingError::NoAnswer("no guard fell asleep"))
+    }
+}
+
+pub struct Day05;
+
+impl Day for Day05 {
+    const NUMBER: u32 = 5;
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String, BindingError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(polymer: String) -> Result<String, BindingError> {
+        let result = day5::parse_polymer(&polymer, None);
+        Ok(result.chars().count().to_string())
+    }
+
+    fn part2(polymer: String) -> Result<String, BindingError> {
+        let (_, result) = day5::improve_polymer(&polymer);
+        Ok(result.chars().count().to_string())
+    }
+}
+
+pub struct Day06;
+
+impl Day for Day06 {
+    const NUMBER: u32 = 6;
+
+    type Input = Vec<day6::coord::Coord>;
+
+    fn parse(input: &str) -> Result<Self::Input, BindingError> {
+        parse_lines(input)
+    }
+
+    fn part1(coordinates: Self::Input) -> Result<String, BindingError> {
+        let bottom_right = day6::get_bottom_right(&coordinates);
+        day6::solution1(&coordinates, &bottom_right)
+            .map(|area| area.to_string())
+            .ok_or(BindingError::NoAnswer("every area is infinite"))
+    }
+
+    fn part2(coordinates: Self::Input) -> Result<String, BindingError> {
+        let bottom_right = day6::get_bottom_right(&coordinates);
+        Ok(day6::solution2(&coordinates, &bottom_right, 10000).to_string())
+    }
+}
+
+pub struct Day07;
+
+impl Day for Day07 {
+    const NUMBER: u32 = 7;
+
+    type Input = day7::Dag;
+
+    fn parse(input: &str) -> Result<Self::Input, BindingError> {
+        Ok(day7::process_dag(input))
+    }
+
+    fn part1(graph: Self::Input) -> Result<String, BindingError> {
+        let order = day7::topological_sort(&graph);
+        Ok(order.iter().map(|s| s.0).collect())
+    }
+}
+
+pub struct Day08;
+
+impl Day for Day08 {
+    const NUMBER: u32 = 8;
+
+    type Input = day8::node::Node;
+
+    fn parse(input: &str) -> Result<Self::Input, BindingError> {
+        input.parse().map_err(invalid)
+    }
+
+    fn part1(node: Self::Input) -> Result<String, BindingError> {
+        Ok(node.metadata_sum().to_string())
+    }
+
+    fn part2(node: Self::Input) -> Result<String, BindingError> {
+        Ok(node.value().to_string())
+    }
+}
+
+pub struct Day09;
+
+impl Day for Day09 {
+    const NUMBER: u32 = 9;
+
+    type Input = day9::config::Config;
+
+    fn parse(input: &str) -> Result<Self::Input, BindingError> {
+        input.parse().map_err(invalid)
+    }
+
+    fn part1(config: Self::Input) -> Result<String, BindingError> {
+        highest_score(config)
+    }
+
+    fn part2(mut config: Self::Input) -> Result<String, BindingError> {
+        config.max_points *= 100;
+        highest_score(config)
+    }
+}
+
+fn highest_score(config: day9::config::Config) -> Result<String, BindingError> {
+    let mut game = day9::game::Game::new(config);
+    game.play_all();
+    game.highest_score()
+        .map(|score| score.to_string())
+        .ok_or(BindingError::NoAnswer("there are no players"))
+}
+
+pub struct Day10;
+
+impl Day for Day10 {
+    const NUMBER: u32 = 10;
+
+    type Input = Vec<day10::light::Light>;
+
+    fn parse(input: &str) -> Result<Self::Input, BindingError> {
+        parse_lines(input)
+    }
+
+    /// The message is read by eye, so the rendered lights are the answer
+    fn part1(mut lights: Self::Input) -> Result<String, BindingError> {
+        day10::find_correct_state(&mut lights);
+        Ok(day10::render(&lights))
+    }
+
+    fn part2(mut lights: Self::Input) -> Result<String, BindingError> {
+        Ok(day10::find_correct_state(&mut lights).to_string())
+    }
+}
+
+pub struct Day11;
+
+impl Day for Day11 {
+    const NUMBER: u32 = 11;
+
+    type Input = day11::grid::Grid;
+
+    /// The binary has its serial number built in, so here it is the input
+    fn parse(input: &str) -> Result<Self::Input, BindingError> {
+        let serial_number = input.parse().map_err(invalid)?;
+        Ok(day11::grid::Grid::generate(300, 300, serial_number))
+    }
+
+    fn part1(grid: Self::Input) -> Result<String, BindingError> {
+        grid.find_max_power_point()
+            .0
+            .map(|point| format!("{},{}", point.x, point.y))
+            .ok_or(BindingError::NoAnswer("the grid is empty"))
+    }
+
+    fn part2(grid: Self::Input) -> Result<String, BindingError> {
+        grid.find_max_power_point_adjustable()
+            .0
+            .map(|(point, size)| format!("{},{},{}", point.x, point.y, size))
+            .ok_or(BindingError::NoAnswer("the grid is empty"))
+    }
+}
+
+pub struct Day12;
+
+impl Day for Day12 {
+    const NUMBER: u32 = 12;
+
+    type Input = (VecDeque<char>, HashSet<String>);
+
+    fn parse(input: &str) -> Result<Self::Input, BindingError> {
+        let mut lines = input.lines();
+        let state = lines
+            .next()
+            .and_then(day12::get_state)
+            .ok_or_else(|| invalid("Missing initial state"))?;
+        let rules = day12::get_rules(&mut lines).ok_or_else(|| invalid("Malformed rule"))?;
+        Ok((state, rules))
+    }
+
+    fn part1((state, rules): Self::Input) -> Result<String, BindingError> {
+        Ok(day12::get_plant_count(&state, &rules).to_string())
+    }
+
+    fn part2((state, rules): Self::Input) -> Result<String, BindingError> {
+        Ok(day12::get_plant_sum(&state, &rules, 50_000_000_000).to_string())
+    }
+}
+
+pub struct Day13;
+
+impl Day for Day13 {
+    const NUMBER: u32 = 13;
+    const WHITESPACE: Whitespace = Whitespace::Significant;
+
+    type Input = day13::map::Map;
+
+    fn parse(input: &str) -> Result<Self::Input, BindingError> {
+        input.parse().map_err(invalid)
+    }
+
+    fn part1(mut map: Self::Input) -> Result<String, BindingError> {
+        let position = map.run_until_collission(400).map_err(invalid)?;
+        Ok(format!("{},{}", position.x, position.y))
+    }
+
+    fn part2(mut map: Self::Input) -> Result<String, BindingError> {
+        match map.run_until_last_cart(100000) {
+            Ok(position) => Ok(format!("{},{}", position.x, position.y)),
+            Err(day13::map::MapError::NoCartsLeft) => {
+                Err(BindingError::NoAnswer("every cart crashed"))
+            }
+            Err(err) => Err(invalid(err)),
+        }
+    }
+}
+
+pub struct Day14;
+
+impl Day for Day14 {
+    const NUMBER: u32 = 14;
+
+    /// The binary has its recipe count built in, so here it is the input
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String, BindingError> {
+        input.parse::<usize>().map_err(invalid)?;
+        Ok(input.to_string())
+    }
+
+    fn part1(input: String) -> Result<String, BindingError> {
+        let n = input.parse().map_err(invalid)?;
+        Ok(day14::improve_recipes(n))
+    }
+
+    fn part2(input: String) -> Result<String, BindingError> {
+        Ok(day14::find_recipes(&input).to_string())
+    }
+}
+
+pub struct Day15;
+
+impl Day for Day15 {
+    const NUMBER: u32 = 15;
+    const WHITESPACE: Whitespace = Whitespace::Significant;
+
+    type Input = day15::game::Game;
+
+    fn parse(input: &str) -> Result<Self::Input, BindingError> {
+        input.parse().map_err(invalid)
+    }
+
+    fn part1(mut game: Self::Input) -> Result<String, BindingError> {
+        let rounds = game.play(u64::MAX);
+        Ok((rounds * game.total_hp_remaining() as u64).to_string())
+    }
+}
@@ -0,0 +1,239 @@
+//! Functions exported from the wasm module. Input text is written into
+//! memory handed out by `aoc_alloc`, and anything returned as text (answers,
+//! diagnostics and renders) is left in an output buffer which the caller
+//! reads through `aoc_output_ptr` and `aoc_output_len`. js/aoc.mjs wraps
+//! all of this up for JavaScript callers.
+use crate::simulation::Simulation;
+use crate::BindingError;
+use std::cell::RefCell;
+use std::slice;
+use std::str;
+
+thread_local! {
+    static OUTPUT: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+fn set_output(text: String) {
+    OUTPUT.with(|output| *output.borrow_mut() = text);
+}
+
+/// Leaves the answer or the diagnostic in the output buffer,
+/// returning 0 on success and 1 otherwise
+fn respond(result: Result<String, BindingError>) -> i32 {
+    match result {
+        Ok(text) => {
+            set_output(text);
+            0
+        }
+        Err(err) => {
+            set_output(err.to_string());
+            1
+        }
+    }
+}
+
+/// # Safety
+///
+/// `ptr` must point to `len` bytes which stay valid for the returned lifetime
+unsafe fn input<'a>(ptr: *const u8, len: usize) -> Result<&'a str, BindingError> {
+    if len == 0 {
+        return Ok("");
+    }
+
+    str::from_utf8(slice::from_raw_parts(ptr, len))
+        .map_err(|_| BindingError::InvalidInput("the input is not valid UTF-8".to_string()))
+}
+
+/// Allocates `len` bytes for the caller to write input into
+#[no_mangle]
+pub extern "C" fn aoc_alloc(len: usize) -> *mut u8 {
+    let mut buffer = Vec::<u8>::with_capacity(len);
+    let ptr = buffer.as_mut_ptr();
+    std::mem::forget(buffer);
+    ptr
+}
+
+/// # Safety
+///
+/// `ptr` and `len` must have come from a single call to `aoc_alloc`
+#[no_mangle]
+pub unsafe extern "C" fn aoc_dealloc(ptr: *mut u8, len: usize) {
+    drop(Vec::from_raw_parts(ptr, 0, len));
+}
+
+/// Start of the output left by the last call. Only valid until the next call
+#[no_mangle]
+pub extern "C" fn aoc_output_ptr() -> *const u8 {
+    OUTPUT.with(|output| output.borrow().as_ptr())
+}
+
+/// Length in bytes of the output left by the last call
+#[no_mangle]
+pub extern "C" fn aoc_output_len() -> usize {
+    OUTPUT.with(|output| output.borrow().len())
+}
+
+/// Checks that the input parses for the given day. On failure
+/// the diagnostic is left in the output buffer
+///
+/// # Safety
+///
+/// `ptr` must point to `len` readable bytes
+#[no_mangle]
+pub unsafe extern "C" fn aoc_parse(day: u32, ptr: *const u8, len: usize) -> i32 {
+    respond(input(ptr, len).and_then(|input| crate::parse(day, input).map(|_| String::new())))
+}
+
+/// Solves a part of a day, leaving the answer or a diagnostic in the output buffer
+///
+/// # Safety
+///
+/// `ptr` must point to `len` readable bytes
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: u32, part: u32, ptr: *const u8, len: usize) -> i32 {
+    respond(input(ptr, len).and_then(|input| crate::solve(day, part, input)))
+}
+
+/// Starts a step-wise simulation of day 13 or 15. Returns null if the
+/// simulation could not be started, leaving a diagnostic in the output buffer
+///
+/// # Safety
+///
+/// `ptr` must point to `len` readable bytes
+#[no_mangle]
+pub unsafe extern "C" fn aoc_simulation_new(
+    day: u32,
+    ptr: *const u8,
+    len: usize,
+) -> *mut Simulation {
+    match input(ptr, len).and_then(|input| Simulation::new(day, input)) {
+        Ok(simulation) => Box::into_raw(Box::new(simulation)),
+        Err(err) => {
+            set_output(err.to_string());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Returns 1 if a step was taken, 0 once the simulation has finished
+/// and -1 on error, leaving the diagnostic in the output buffer
+///
+/// # Safety
+///
+/// `simulation` must have come from `aoc_simulation_new` and not been freed
+#[no_mangle]
+pub unsafe extern "C" fn aoc_simulation_step(simulation: *mut Simulation) -> i32 {
+    match (*simulation).step() {
+        Ok(stepped) => stepped as i32,
+        Err(err) => {
+            set_output(err.to_string());
+            -1
+        }
+    }
+}
+
+/// # Safety
+///
+/// `simulation` must have come from `aoc_simulation_new` and not been freed
+#[no_mangle]
+pub unsafe extern "C" fn aoc_simulation_steps(simulation: *const Simulation) -> u32 {
+    (*simulation).steps() as u32
+}
+
+/// Leaves the current state of the map in the output buffer
+///
+/// # Safety
+///
+/// `simulation` must have come from `aoc_simulation_new` and not been freed
+#[no_mangle]
+pub unsafe extern "C" fn aoc_simulation_render(simulation: *const Simulation) {
+    set_output((*simulation).render());
+}
+
+/// Leaves a one line summary of the simulation in the output buffer
+///
+/// # Safety
+///
+/// `simulation` must have come from `aoc_simulation_new` and not been freed
+#[no_mangle]
+pub unsafe extern "C" fn aoc_simulation_summary(simulation: *const Simulation) {
+    set_output((*simulation).summary());
+}
+
+/// # Safety
+///
+/// `simulation` must have come from `aoc_simulation_new` and not been freed
+#[no_mangle]
+pub unsafe extern "C" fn aoc_simulation_free(simulation: *mut Simulation) {
+    drop(Box::from_raw(simulation));
+}
+
+#[cfg(test)]
+mod test_ffi {
+    use super::*;
+
+    fn output() -> String {
+        let bytes = unsafe { slice::from_raw_parts(aoc_output_ptr(), aoc_output_len()) };
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    fn with_input<T>(text: &str, f: impl FnOnce(*const u8, usize) -> T) -> T {
+        let ptr = aoc_alloc(text.len());
+        unsafe {
+            std::ptr::copy_nonoverlapping(text.as_ptr(), ptr, text.len());
+        }
+        let result = f(ptr, text.len());
+        unsafe { aoc_dealloc(ptr, text.len()) };
+        result
+    }
+
+    #[test]
+    fn test_solve() {
+        let status = with_input("+1\n+1\n-2", |ptr, len| unsafe {
+            aoc_solve(1, 2, ptr, len)
+        });
+        assert_eq!(status, 0);
+        assert_eq!(output(), "0");
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let status = with_input("+1\n+x", |ptr, len| unsafe { aoc_parse(1, ptr, len) });
+        assert_eq!(status, 1);
+        assert!(output().starts_with("Invalid input: "));
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let bytes = [0xff, 0xfe];
+        let status = unsafe { aoc_solve(1, 1, bytes.as_ptr(), bytes.len()) };
+        assert_eq!(status, 1);
+        assert_eq!(output(), "Invalid input: the input is not valid UTF-8");
+    }
+
+    #[test]
+    fn test_simulation() {
+        let map = "|\nv\n|\n|\n|\n^\n|";
+        let simulation = with_input(map, |ptr, len| unsafe { aoc_simulation_new(13, ptr, len) });
+        assert!(!simulation.is_null());
+
+        unsafe {
+            assert_eq!(aoc_simulation_step(simulation), 1);
+            assert_eq!(aoc_simulation_step(simulation), 1);
+            assert_eq!(aoc_simulation_step(simulation), 0);
+            assert_eq!(aoc_simulation_steps(simulation), 2);
+
+            aoc_simulation_summary(simulation);
+            assert_eq!(output(), "tick 2, 0 carts, first crash at 0,3");
+
+            aoc_simulation_free(simulation);
+        }
+    }
+
+    #[test]
+    fn test_simulation_error() {
+        let simulation = with_input("", |ptr, len| unsafe { aoc_simulation_new(14, ptr, len) });
+        assert!(simulation.is_null());
+        assert_eq!(output(), "There is no day 14");
+    }
+}
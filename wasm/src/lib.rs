@@ -0,0 +1,107 @@
+//! Bindings which let every day be solved from a wasm32-unknown-unknown
+//! build of this crate. The safe API in this module is what the exported
+//! functions in `ffi` call into, and is what the native tests exercise.
+use std::fmt;
+
+pub mod days;
+pub mod ffi;
+pub mod simulation;
+
+#[derive(Debug, PartialEq)]
+pub enum BindingError {
+    UnknownDay(u32),
+    UnknownPart(u32),
+    Unsolved(u32, u32),
+    InvalidInput(String),
+    NoAnswer(&'static str),
+}
+
+impl std::error::Error for BindingError {}
+
+impl fmt::Display for BindingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BindingError::UnknownDay(day) => write!(f, "There is no day {}", day),
+            BindingError::UnknownPart(part) => write!(f, "There is no part {}", part),
+            BindingError::Unsolved(day, part) => {
+                write!(f, "Part {} of day {} has not been solved", part, day)
+            }
+            BindingError::InvalidInput(message) => write!(f, "Invalid input: {}", message),
+            BindingError::NoAnswer(reason) => write!(f, "No answer: {}", reason),
+        }
+    }
+}
+
+/// Checks that the input can be parsed for the given day
+pub fn parse(day: u32, input: &str) -> Result<(), BindingError> {
+    days::parse(day, input)
+}
+
+/// Solves one part of a day, returning the answer as it would be submitted
+pub fn solve(day: u32, part: u32, input: &str) -> Result<String, BindingError> {
+    days::solve(day, part, input)
+}
+
+#[cfg(test)]
+mod test_bindings {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(
+        day,
+        part,
+        input,
+        expected,
+        case(1, 1, "+1\n-2\n+3\n+1", "3"),
+        case(1, 2, "+1\n-2\n+3\n+1", "2"),
+        case(2, 2, "abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz", "fgij"),
+        case(3, 1, "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2", "4"),
+        case(3, 2, "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2", "3"),
+        case(5, 1, "dabAcCaCBAcCcaDA", "10"),
+        case(5, 2, "dabAcCaCBAcCcaDA", "4"),
+        case(8, 1, "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2", "138"),
+        case(8, 2, "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2", "66"),
+        case(9, 1, "10 players; last marble is worth 1618 points", "8317"),
+        case(11, 1, "18", "33,45"),
+        case(14, 1, "9", "5158916779"),
+        case(14, 2, "51589", "9")
+    )]
+    fn test_solve(day: u32, part: u32, input: &str, expected: &str) {
+        assert_eq!(solve(day, part, input), Ok(expected.to_string()));
+    }
+
+    #[rstest(
+        day,
+        part,
+        expected,
+        case(0, 1, BindingError::UnknownDay(0)),
+        case(16, 1, BindingError::UnknownDay(16)),
+        case(1, 3, BindingError::UnknownPart(3)),
+        case(7, 2, BindingError::Unsolved(7, 2)),
+        case(15, 2, BindingError::Unsolved(15, 2))
+    )]
+    fn test_solve_errors(day: u32, part: u32, expected: BindingError) {
+        assert_eq!(solve(day, part, ""), Err(expected));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(3, "#1 @ 1,3: 4x4"), Ok(()));
+        assert!(matches!(
+            parse(3, "#1 @ 1,3 4x4"),
+            Err(BindingError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            parse(11, "abc"),
+            Err(BindingError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_crlf() {
+        assert_eq!(
+            solve(1, 1, "\u{feff}+1\r\n-2\r\n+3\r\n+1\r\n"),
+            Ok("3".to_string())
+        );
+    }
+}
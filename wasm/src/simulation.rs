@@ -0,0 +1,166 @@
+//! Step-wise access to the two simulations, so they can be played back a
+//! tick or round at a time instead of only reporting the final answer.
+use crate::days::invalid;
+use crate::BindingError;
+use common::input::{normalise, Whitespace};
+use day13::cart::Position;
+use day13::map::Map;
+use day15::game::Game;
+use day15::race::Race;
+
+pub enum Simulation {
+    /// Day 13: each step is a tick of every cart moving once
+    Carts { map: Map, crashes: Vec<Position> },
+    /// Day 15: each step is a round of every unit acting once
+    Combat { game: Game, rounds: u64 },
+}
+
+impl Simulation {
+    pub fn new(day: u32, input: &str) -> Result<Simulation, BindingError> {
+        let input = normalise(input, Whitespace::Significant);
+        match day {
+            13 => Ok(Simulation::Carts {
+                map: input.parse().map_err(invalid)?,
+                crashes: vec![],
+            }),
+            15 => Ok(Simulation::Combat {
+                game: input.parse().map_err(invalid)?,
+                rounds: 0,
+            }),
+            _ => Err(BindingError::UnknownDay(day)),
+        }
+    }
+
+    /// Carts stop once at most one is left, units once only one race remains
+    pub fn finished(&self) -> bool {
+        match self {
+            Simulation::Carts { map, .. } => map.carts.len() <= 1,
+            Simulation::Combat { game, .. } => game.game_completed(),
+        }
+    }
+
+    /// Advances by a single step. Returns false once the simulation
+    /// has finished, in which case nothing is changed
+    pub fn step(&mut self) -> Result<bool, BindingError> {
+        if self.finished() {
+            return Ok(false);
+        }
+
+        match self {
+            Simulation::Carts { map, crashes } => {
+                crashes.extend(map.run().map_err(invalid)?);
+            }
+            Simulation::Combat { game, rounds } => {
+                if game.round() {
+                    *rounds += 1;
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Number of steps taken so far. For combat this only counts full rounds
+    pub fn steps(&self) -> u64 {
+        match self {
+            Simulation::Carts { map, .. } => map.ticks as u64,
+            Simulation::Combat { rounds, .. } => *rounds,
+        }
+    }
+
+    pub fn render(&self) -> String {
+        match self {
+            Simulation::Carts { map, .. } => map.print(),
+            Simulation::Combat { game, .. } => game.render_map(),
+        }
+    }
+
+    /// One line describing the state of the simulation
+    pub fn summary(&self) -> String {
+        match self {
+            Simulation::Carts { map, crashes } => {
+                let mut summary = format!("tick {}, {} carts", map.ticks, map.carts.len());
+                if let Some(first) = crashes.first() {
+                    summary += &format!(", first crash at {},{}", first.x, first.y);
+                }
+                summary
+            }
+            Simulation::Combat { game, rounds } => format!(
+                "round {}, {} elves, {} goblins, {} hit points",
+                rounds,
+                game.count(Race::Elf),
+                game.count(Race::Goblin),
+                game.total_hp_remaining()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_simulation {
+    use super::*;
+
+    const CARTS: &str = r"/>-<\
+|   |
+| /<+-\
+| | | v
+\>+</ |
+  |   ^
+  \<->/";
+
+    const COMBAT: &str = "#######
+#.G...#
+#...EG#
+#.#.#G#
+#..G#E#
+#.....#
+#######";
+
+    fn run(simulation: &mut Simulation) -> Result<(), BindingError> {
+        while simulation.step()? {}
+        Ok(())
+    }
+
+    #[test]
+    fn test_carts() -> Result<(), BindingError> {
+        let mut simulation = Simulation::new(13, CARTS)?;
+        assert_eq!(simulation.summary(), "tick 0, 9 carts");
+
+        run(&mut simulation)?;
+
+        assert!(simulation.finished());
+        assert!(!simulation.step()?);
+        assert_eq!(simulation.summary(), "tick 3, 1 carts, first crash at 2,0");
+        Ok(())
+    }
+
+    #[test]
+    fn test_combat() -> Result<(), BindingError> {
+        let mut simulation = Simulation::new(15, COMBAT)?;
+
+        run(&mut simulation)?;
+
+        // 47 full rounds with 590 hit points left gives 27730
+        assert_eq!(simulation.steps(), 47);
+        assert_eq!(
+            simulation.summary(),
+            "round 47, 0 elves, 4 goblins, 590 hit points"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_render() -> Result<(), BindingError> {
+        let simulation = Simulation::new(15, COMBAT)?;
+        assert_eq!(simulation.render().trim_end(), COMBAT);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        assert!(matches!(
+            Simulation::new(12, ""),
+            Err(BindingError::UnknownDay(12))
+        ));
+    }
+}
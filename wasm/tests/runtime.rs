@@ -0,0 +1,121 @@
+// Loads the built module into a wasm interpreter and drives it through its
+// exports, the same way the JavaScript wrapper does. Build it first with
+//   cargo build --release --target wasm32-unknown-unknown
+// and then run these with `cargo test -- --ignored`
+use wasmi::{Engine, Instance, Linker, Memory, Module, Store};
+
+const MODULE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/target/wasm32-unknown-unknown/release/wasm.wasm"
+);
+
+struct Runtime {
+    store: Store<()>,
+    instance: Instance,
+    memory: Memory,
+}
+
+impl Runtime {
+    fn load() -> Runtime {
+        let engine = Engine::default();
+        let bytes = std::fs::read(MODULE).expect("the module has not been built");
+        let module = Module::new(&engine, &bytes[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = Linker::<()>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let memory = instance.get_memory(&store, "memory").unwrap();
+
+        Runtime {
+            store,
+            instance,
+            memory,
+        }
+    }
+
+    fn call<P: wasmi::WasmParams, R: wasmi::WasmResults>(&mut self, name: &str, params: P) -> R {
+        self.instance
+            .get_typed_func::<P, R>(&self.store, name)
+            .unwrap()
+            .call(&mut self.store, params)
+            .unwrap()
+    }
+
+    /// Copies the text into the module's memory, returning its pointer and length
+    fn write(&mut self, text: &str) -> (i32, i32) {
+        let len = text.len() as i32;
+        let ptr: i32 = self.call("aoc_alloc", len);
+        self.memory
+            .write(&mut self.store, ptr as usize, text.as_bytes())
+            .unwrap();
+        (ptr, len)
+    }
+
+    fn output(&mut self) -> String {
+        let ptr: i32 = self.call("aoc_output_ptr", ());
+        let len: i32 = self.call("aoc_output_len", ());
+        let mut buffer = vec![0; len as usize];
+        self.memory
+            .read(&self.store, ptr as usize, &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn solve(&mut self, day: i32, part: i32, input: &str) -> (i32, String) {
+        let (ptr, len) = self.write(input);
+        let status: i32 = self.call("aoc_solve", (day, part, ptr, len));
+        self.call::<_, ()>("aoc_dealloc", (ptr, len));
+        (status, self.output())
+    }
+}
+
+#[test]
+#[ignore = "needs the module built for wasm32-unknown-unknown"]
+fn test_solve() {
+    let mut runtime = Runtime::load();
+
+    assert_eq!(runtime.solve(1, 1, "+1\n-2\n+3\n+1"), (0, "3".to_string()));
+    assert_eq!(
+        runtime.solve(5, 2, "dabAcCaCBAcCcaDA"),
+        (0, "4".to_string())
+    );
+    assert_eq!(runtime.solve(14, 1, "9"), (0, "5158916779".to_string()));
+}
+
+#[test]
+#[ignore = "needs the module built for wasm32-unknown-unknown"]
+fn test_diagnostics() {
+    let mut runtime = Runtime::load();
+
+    assert_eq!(
+        runtime.solve(16, 1, ""),
+        (1, "There is no day 16".to_string())
+    );
+    assert_eq!(
+        runtime.solve(7, 2, "Step C must be finished before step A can begin."),
+        (1, "Part 2 of day 7 has not been solved".to_string())
+    );
+}
+
+#[test]
+#[ignore = "needs the module built for wasm32-unknown-unknown"]
+fn test_simulation() {
+    let mut runtime = Runtime::load();
+
+    let (ptr, len) = runtime.write("#######\n#.G...#\n#...EG#\n#.#.#G#\n#..G#E#\n#.....#\n#######");
+    let simulation: i32 = runtime.call("aoc_simulation_new", (15, ptr, len));
+    runtime.call::<_, ()>("aoc_dealloc", (ptr, len));
+    assert_ne!(simulation, 0);
+
+    while runtime.call::<_, i32>("aoc_simulation_step", simulation) == 1 {}
+
+    runtime.call::<_, ()>("aoc_simulation_summary", simulation);
+    assert_eq!(
+        runtime.output(),
+        "round 47, 0 elves, 4 goblins, 590 hit points"
+    );
+
+    runtime.call::<_, ()>("aoc_simulation_free", simulation);
+}